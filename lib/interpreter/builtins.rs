@@ -0,0 +1,182 @@
+use crate::interpreter::enviroment::Environment;
+use crate::interpreter::object::{HashKey, NativeFunction, Object};
+use crate::interpreter::runtime_error::InterpreterError;
+
+/// Register every native function in the given (global) environment.
+pub fn define_globals(environment: &mut Environment) {
+    let natives = [
+        NativeFunction { name: "len", arity: 1, function: len },
+        NativeFunction { name: "keys", arity: 1, function: keys },
+        NativeFunction { name: "has", arity: 2, function: has },
+        NativeFunction { name: "remove", arity: 2, function: remove },
+        NativeFunction { name: "set", arity: 1, function: set },
+        NativeFunction { name: "add", arity: 2, function: add },
+        NativeFunction { name: "variants", arity: 1, function: variants },
+        NativeFunction { name: "range", arity: 3, function: range },
+    ];
+
+    for native in natives {
+        environment.define(native.name.to_string(), Object::NativeFunction(native));
+    }
+}
+
+// The hashable second argument shared by `has`, `remove` and `add`
+fn key_argument(arguments: &[Object], line: usize) -> Result<HashKey, InterpreterError> {
+    arguments[1].to_key().ok_or(InterpreterError::UnhashableKey { line })
+}
+
+// The error for a first argument that is neither a map nor a set
+fn not_a_collection(name: &str, line: usize) -> InterpreterError {
+    InterpreterError::InvalidArgument {
+        message: format!("{}() expects a map or set as its first argument.", name),
+        line,
+    }
+}
+
+// len(value): number of elements in a list, map or set, or characters in a string
+fn len(arguments: &[Object], line: usize) -> Result<Object, InterpreterError> {
+    match &arguments[0] {
+        Object::List(elements) => Ok(Object::Integer(elements.borrow().len() as i64)),
+        Object::Map(entries) => Ok(Object::Integer(entries.borrow().len() as i64)),
+        Object::Set(elements) => Ok(Object::Integer(elements.borrow().len() as i64)),
+        Object::String(s) => Ok(Object::Integer(s.chars().count() as i64)),
+        _ => Err(InterpreterError::InvalidArgument {
+            message: "len() expects a list, map, set or string.".to_string(),
+            line,
+        }),
+    }
+}
+
+// keys(map): a new list of the map's keys in insertion order
+fn keys(arguments: &[Object], line: usize) -> Result<Object, InterpreterError> {
+    match &arguments[0] {
+        Object::Map(entries) => Ok(Object::list(
+            entries.borrow().keys().map(HashKey::to_object).collect(),
+        )),
+        _ => Err(InterpreterError::InvalidArgument {
+            message: "keys() expects a map.".to_string(),
+            line,
+        }),
+    }
+}
+
+// has(map, key) or has(set, element): whether the map contains the key or the set the element
+fn has(arguments: &[Object], line: usize) -> Result<Object, InterpreterError> {
+    match &arguments[0] {
+        Object::Map(entries) => Ok(Object::Bool(entries.borrow().contains_key(&key_argument(arguments, line)?))),
+        Object::Set(elements) => Ok(Object::Bool(elements.borrow().contains(&key_argument(arguments, line)?))),
+        _ => Err(not_a_collection("has", line)),
+    }
+}
+
+// remove(map, key): delete the entry and return its value, or nil if it was missing.
+// remove(set, element): delete the element and return whether it was present.
+fn remove(arguments: &[Object], line: usize) -> Result<Object, InterpreterError> {
+    match &arguments[0] {
+        Object::Map(entries) => {
+            let removed = entries.borrow_mut().shift_remove(&key_argument(arguments, line)?);
+            Ok(removed.unwrap_or(Object::Nil))
+        }
+        Object::Set(elements) => {
+            let removed = elements.borrow_mut().shift_remove(&key_argument(arguments, line)?);
+            Ok(Object::Bool(removed))
+        }
+        _ => Err(not_a_collection("remove", line)),
+    }
+}
+
+// set(values): a new set of the elements of a list or set, keys of a map or characters
+// of a string, keeping the first occurrence of each in order
+fn set(arguments: &[Object], line: usize) -> Result<Object, InterpreterError> {
+    let items = match &arguments[0] {
+        Object::List(_) | Object::Map(_) | Object::Set(_) | Object::String(_) => arguments[0].items(),
+        _ => None,
+    };
+    let Some(items) = items else {
+        return Err(InterpreterError::InvalidArgument {
+            message: "set() expects a list, map, set or string.".to_string(),
+            line,
+        });
+    };
+    let elements = items
+        .iter()
+        .map(|item| item.to_key().ok_or(InterpreterError::UnhashableKey { line }))
+        .collect::<Result<_, _>>()?;
+    Ok(Object::set(elements))
+}
+
+// add(set, element): insert the element and return whether it was new
+fn add(arguments: &[Object], line: usize) -> Result<Object, InterpreterError> {
+    let Object::Set(elements) = &arguments[0] else {
+        return Err(InterpreterError::InvalidArgument {
+            message: "add() expects a set as its first argument.".to_string(),
+            line,
+        });
+    };
+    let added = elements.borrow_mut().insert(key_argument(arguments, line)?);
+    Ok(Object::Bool(added))
+}
+
+// variants(enum): a new list of the enum's variants in declaration order
+fn variants(arguments: &[Object], line: usize) -> Result<Object, InterpreterError> {
+    match &arguments[0] {
+        Object::Enum(enumeration) => Ok(Object::list(
+            enumeration.variants.iter().cloned().map(Object::Variant).collect(),
+        )),
+        _ => Err(InterpreterError::InvalidArgument {
+            message: "variants() expects an enum.".to_string(),
+            line,
+        }),
+    }
+}
+
+// The most elements `range()` will build, and the most steps a float `for (x in a..b)` takes;
+// longer integer ranges belong in a lazy `for (i in a..b)`
+pub(crate) const MAX_RANGE_LENGTH: u64 = 10_000_000;
+
+// range(start, stop, step): a list counting from start towards stop (exclusive) by step,
+// of integers when all three arguments are integers and of floats otherwise
+fn range(arguments: &[Object], line: usize) -> Result<Object, InterpreterError> {
+    let invalid = |message: String| InterpreterError::InvalidArgument { message, line };
+    let too_long = || {
+        invalid(format!(
+            "range() would have more than {} elements; use 'for (i in start..stop)' instead.",
+            MAX_RANGE_LENGTH
+        ))
+    };
+
+    if let [Object::Integer(start), Object::Integer(stop), Object::Integer(step)] = arguments {
+        let (start, stop, step) = (*start, *stop, *step);
+        if step == 0 {
+            return Err(invalid("range() step must not be zero.".to_string()));
+        }
+        // Ceiling division in i128, which can't overflow for i64 operands
+        let (distance, step_size) = ((stop as i128 - start as i128) * step.signum() as i128, step.unsigned_abs() as i128);
+        let length = if distance > 0 { (distance + step_size - 1) / step_size } else { 0 };
+        if length > MAX_RANGE_LENGTH as i128 {
+            return Err(too_long());
+        }
+        let values = (0..length as i64).map(|i| Object::Integer(start + i * step)).collect();
+        return Ok(Object::list(values));
+    }
+
+    let number = |value: &Object| value.as_number().map_err(|_| invalid("range() expects numbers.".to_string()));
+    let (start, stop, step) = (number(&arguments[0])?, number(&arguments[1])?, number(&arguments[2])?);
+    if !(start.is_finite() && stop.is_finite() && step.is_finite()) {
+        return Err(invalid("range() expects finite numbers.".to_string()));
+    }
+    if step == 0.0 {
+        return Err(invalid("range() step must not be zero.".to_string()));
+    }
+    let length = ((stop - start) / step).ceil().max(0.0);
+    if length > MAX_RANGE_LENGTH as f64 {
+        return Err(too_long());
+    }
+    // Multiply rather than accumulate, so rounding errors don't build up
+    let values = (0u64..)
+        .map(|i| start + i as f64 * step)
+        .take_while(|value| if step > 0.0 { *value < stop } else { *value > stop })
+        .map(Object::Number)
+        .collect();
+    Ok(Object::list(values))
+}
@@ -1,100 +1,111 @@
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 use crate::token::token::Token;
 use crate::interpreter::object::Object;
 use crate::interpreter::runtime_error::InterpreterError;  // Assuming you have InterpreterError defined
 use anyhow::Result;
 
-// Define the Environment for variable storage
+// Define the Environment for variable storage. Scopes are shared, so a block or loop body
+// assigning to an outer variable updates the same storage the outer scope reads from.
 #[derive(Clone)]
 pub struct Environment {
     values: HashMap<String, Object>,
-    enclosing: Option<Box<Environment>>, 
+    // Names in `values` declared with `const`
+    constants: HashSet<String>,
+    enclosing: Option<Rc<RefCell<Environment>>>,
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Environment {
     pub fn new() -> Self {
         Environment {
             values: HashMap::new(),
+            constants: HashSet::new(),
             enclosing: None,
         }
     }
 
     // Create an environment with an enclosing (parent) environment
-    pub fn from_enclosing(enclosing: Environment) -> Self {
+    pub fn from_enclosing(enclosing: Rc<RefCell<Environment>>) -> Self {
         Environment {
             values: HashMap::new(),
-            enclosing: Some(Box::new(enclosing)),
+            constants: HashSet::new(),
+            enclosing: Some(enclosing),
         }
     }
 
     // Define a new variable
     pub fn define(&mut self, name: String, value: Object) {
+        self.constants.remove(&name);
+        self.values.insert(name, value);
+    }
+
+    // Define a variable that can't be reassigned
+    pub fn define_constant(&mut self, name: String, value: Object) {
+        self.constants.insert(name.clone());
         self.values.insert(name, value);
     }
 
+    // The bindings defined directly in this scope
+    pub fn values(&self) -> &HashMap<String, Object> {
+        &self.values
+    }
+
     // Get the value of a variable, check enclosing environments if necessary
     pub fn get(&self, name: &Token) -> Result<Object, InterpreterError> {
         if let Some(value) = self.values.get(&name.lexeme) {
             return Ok(value.clone());
         }
         if let Some(ref enclosing) = self.enclosing {
-            return enclosing.get(name);
+            return enclosing.borrow().get(name);
         }
         // Use InterpreterError instead of a String for error handling
-        Err(InterpreterError::UndefinedVariable { 
-            name: name.lexeme.clone(), 
-            line: name.line 
+        Err(InterpreterError::UndefinedVariable {
+            name: name.lexeme.clone(),
+            line: name.line
         })
     }
 
     // Assign a new value to a variable, check enclosing environments if necessary
     pub fn assign(&mut self, name: &Token, value: Object) -> Result<(), InterpreterError> {
         if self.values.contains_key(&name.lexeme) {
+            if self.constants.contains(&name.lexeme) {
+                return Err(InterpreterError::ConstantAssignment {
+                    name: name.lexeme.clone(),
+                    line: name.line
+                });
+            }
             self.values.insert(name.lexeme.clone(), value);
             return Ok(());
         }
-        if let Some(ref mut enclosing) = self.enclosing {
-            return enclosing.assign(name, value);
+        if let Some(ref enclosing) = self.enclosing {
+            return enclosing.borrow_mut().assign(name, value);
         }
-        Err(InterpreterError::UndefinedVariable { 
-            name: name.lexeme.clone(), 
-            line: name.line 
+        Err(InterpreterError::UndefinedVariable {
+            name: name.lexeme.clone(),
+            line: name.line
         })
     }
 
-    // Get an ancestor environment by traversing a specified distance
-    pub fn ancestor(&self, distance: usize) -> Option<&Environment> {
-        let mut environment = self;
-        for _ in 0..distance {
-            if let Some(ref enclosing) = environment.enclosing {
-                environment = enclosing;
-            } else {
-                return None;
-            }
-        }
-        Some(environment)
-    }
-
-    // Get a value from an ancestor environment
+    // Get a value from an ancestor environment a specified distance away
     pub fn get_at(&self, distance: usize, name: &str) -> Option<Object> {
-        self.ancestor(distance)?.values.get(name).cloned()
+        if distance == 0 {
+            return self.values.get(name).cloned();
+        }
+        self.enclosing.as_ref()?.borrow().get_at(distance - 1, name)
     }
 
     pub fn assign_at(&mut self, distance: usize, name: &Token, value: Object) {
-        if let Some(environment) = self.ancestor_mut(distance) {
-            environment.values.insert(name.lexeme.clone(), value);
-        }
-    }
-    
-    fn ancestor_mut(&mut self, distance: usize) -> Option<&mut Environment> {
-        let mut environment = self;
-        for _ in 0..distance {
-            if let Some(ref mut enclosing) = environment.enclosing {
-                environment = enclosing;
-            } else {
-                return None;
-            }
+        if distance == 0 {
+            self.values.insert(name.lexeme.clone(), value);
+        } else if let Some(ref enclosing) = self.enclosing {
+            enclosing.borrow_mut().assign_at(distance - 1, name, value);
         }
-        Some(environment)
     }
 }
@@ -1,5 +1,6 @@
-use crate::parser::{Expr, Literal};  // Import the Expr from parser
+use crate::parser::{Expr, FunctionDecl, Literal};  // Import the Expr from parser
 use crate::token::token::Token;
+use std::rc::Rc;
 
 // Expression Visitor Trait
 pub trait ExprVisitor<T> {
@@ -9,6 +10,23 @@ pub trait ExprVisitor<T> {
     fn visit_unary_expr(&mut self, operator: &Token, right: &Expr) -> T;
     fn visit_variable_expr(&mut self, token: &Token) -> T;
     fn visit_assign_expr(&mut self, name: &Token, value: &Expr) -> T;
+    fn visit_interpolation_expr(&mut self, parts: &[Expr]) -> T;
+    fn visit_list_expr(&mut self, elements: &[Expr]) -> T;
+    fn visit_map_expr(&mut self, brace: &Token, entries: &[(Expr, Expr)]) -> T;
+    fn visit_index_expr(&mut self, object: &Expr, bracket: &Token, index: &Expr) -> T;
+    fn visit_index_assign_expr(&mut self, object: &Expr, bracket: &Token, index: &Expr, value: &Expr) -> T;
+    fn visit_call_expr(&mut self, callee: &Expr, paren: &Token, arguments: &[Expr]) -> T;
+    fn visit_get_expr(&mut self, object: &Expr, name: &Token) -> T;
+    fn visit_conditional_expr(&mut self, condition: &Expr, then_branch: &Expr, else_branch: &Expr) -> T;
+    fn visit_comma_expr(&mut self, left: &Expr, right: &Expr) -> T;
+    fn visit_compound_assign_expr(&mut self, target: &Expr, operator: &Token, value: &Expr) -> T;
+    fn visit_prefix_update_expr(&mut self, operator: &Token, target: &Expr) -> T;
+    fn visit_postfix_update_expr(&mut self, target: &Expr, operator: &Token) -> T;
+    fn visit_lambda_expr(&mut self, function: &Rc<FunctionDecl>) -> T;
+    fn visit_nil_coalesce_expr(&mut self, left: &Expr, operator: &Token, right: &Expr) -> T;
+    fn visit_optional_get_expr(&mut self, object: &Expr, name: &Token) -> T;
+    fn visit_optional_chain_expr(&mut self, chain: &Expr) -> T;
+    fn visit_list_assign_expr(&mut self, names: &[Token], equals: &Token, value: &Expr) -> T;
 }
 
 // Implement the `accept` method for `Expr` using the visitor pattern
@@ -21,6 +39,29 @@ impl Expr {
             Expr::Unary(operator, right) => visitor.visit_unary_expr(operator, right),
             Expr::Variable(token) => visitor.visit_variable_expr(token),
             Expr::Assign(name, value) => visitor.visit_assign_expr(name, value),
+            Expr::Interpolation(parts) => visitor.visit_interpolation_expr(parts),
+            Expr::List(elements) => visitor.visit_list_expr(elements),
+            Expr::Map(brace, entries) => visitor.visit_map_expr(brace, entries),
+            Expr::Index(object, bracket, index) => visitor.visit_index_expr(object, bracket, index),
+            Expr::IndexAssign(object, bracket, index, value) => {
+                visitor.visit_index_assign_expr(object, bracket, index, value)
+            }
+            Expr::Call(callee, paren, arguments) => visitor.visit_call_expr(callee, paren, arguments),
+            Expr::Get(object, name) => visitor.visit_get_expr(object, name),
+            Expr::Conditional(condition, then_branch, else_branch) => {
+                visitor.visit_conditional_expr(condition, then_branch, else_branch)
+            }
+            Expr::Comma(left, right) => visitor.visit_comma_expr(left, right),
+            Expr::CompoundAssign(target, operator, value) => {
+                visitor.visit_compound_assign_expr(target, operator, value)
+            }
+            Expr::PrefixUpdate(operator, target) => visitor.visit_prefix_update_expr(operator, target),
+            Expr::PostfixUpdate(target, operator) => visitor.visit_postfix_update_expr(target, operator),
+            Expr::Lambda(function) => visitor.visit_lambda_expr(function),
+            Expr::NilCoalesce(left, operator, right) => visitor.visit_nil_coalesce_expr(left, operator, right),
+            Expr::OptionalGet(object, name) => visitor.visit_optional_get_expr(object, name),
+            Expr::OptionalChain(chain) => visitor.visit_optional_chain_expr(chain),
+            Expr::ListAssign(names, equals, value) => visitor.visit_list_assign_expr(names, equals, value),
         }
     }
 }
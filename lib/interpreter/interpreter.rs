@@ -1,24 +1,136 @@
 use crate::interpreter::enviroment::Environment;
-use crate::parser::{Expr, Stmt, Literal};
-use crate::interpreter::object::Object;
-use crate::token::token::{Token, TokenType};
+use crate::parser::{Binding, CatchClause, Expr, FunctionDecl, MatchArm, Pattern, Stmt, Literal};
+use crate::interpreter::object::{float_to_integer, Enum, Function, HashKey, Module, Object};
+use crate::token::token::{Literal as TokenLiteral, Token, TokenType};
 use crate::interpreter::runtime_error::InterpreterError;
 use crate::interpreter::expr::ExprVisitor;
 use crate::interpreter::stmt::StmtVisitor;
+use crate::interpreter::builtins::{define_globals, MAX_RANGE_LENGTH};
+use crate::interpreter::resolver::Resolver;
+use crate::token::tokenizer::tokenize;
+use crate::token::tokenizer_error::TokenizerError;
+use crate::parser::Parser;
+use bytes::Bytes;
+use indexmap::IndexMap;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// How a statement finished executing: normally, by jumping out of the
+/// innermost loop body with `break` or `continue`, or by returning from a function.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlFlow {
+    Normal,
+    Break,
+    Continue,
+    Return(Object),
+}
+
+/// The operator that holds when a comparison fails, for assertion messages.
+fn comparison_negation(operator: &TokenType) -> Option<&'static str> {
+    match operator {
+        TokenType::Equal_Equal => Some("!="),
+        TokenType::Bang_Equal => Some("=="),
+        TokenType::Less => Some(">="),
+        TokenType::Less_Equal => Some(">"),
+        TokenType::Greater => Some("<="),
+        TokenType::Greater_Equal => Some("<"),
+        _ => None,
+    }
+}
+
+/// Two numeric operands after promotion: both integers, or both floats when either is one.
+enum NumericPair {
+    Integers(i64, i64),
+    Floats(f64, f64),
+}
+
+impl NumericPair {
+    fn of(left: &Object, right: &Object) -> Option<NumericPair> {
+        match (left, right) {
+            (Object::Integer(left), Object::Integer(right)) => Some(NumericPair::Integers(*left, *right)),
+            _ => Some(NumericPair::Floats(left.as_number().ok()?, right.as_number().ok()?)),
+        }
+    }
+}
+
+/// Order two numbers exactly, like `==` does, so `2^53 + 1 > 2^53` holds even against a
+/// float. `None` if either side isn't a number or is NaN.
+fn compare_numbers(left: &Object, right: &Object) -> Option<Ordering> {
+    match (left, right) {
+        (Object::Integer(left), Object::Integer(right)) => Some(left.cmp(right)),
+        (Object::Number(left), Object::Number(right)) => left.partial_cmp(right),
+        (Object::Integer(left), Object::Number(right)) => compare_integer_to_float(*left, *right),
+        (Object::Number(left), Object::Integer(right)) => compare_integer_to_float(*right, *left).map(Ordering::reverse),
+        _ => None,
+    }
+}
+
+fn compare_integer_to_float(integer: i64, float: f64) -> Option<Ordering> {
+    // 2^63, the first float past i64::MAX
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+    if float.is_nan() {
+        return None;
+    }
+    if float >= LIMIT {
+        return Some(Ordering::Less);
+    }
+    if float < -LIMIT {
+        return Some(Ordering::Greater);
+    }
+    // In range, so the whole part converts to i64 exactly; a fraction puts the float above it
+    let whole = float.floor();
+    let fraction = if float > whole { Ordering::Less } else { Ordering::Equal };
+    Some(integer.cmp(&(whole as i64)).then(fraction))
+}
 
 pub struct Interpreter {
-    environment: Environment,
+    environment: Rc<RefCell<Environment>>,
+    // Native functions only; every file's top-level scope encloses this one
+    globals: Rc<RefCell<Environment>>,
     evaluate_mode: bool,
+    // Cleared by `--no-asserts`, making `assert` statements do nothing
+    asserts_enabled: bool,
+    // The file being executed, used to resolve relative import paths
+    current_file: Option<PathBuf>,
+    // Imported modules by canonical path, so each file runs only once
+    modules: HashMap<PathBuf, Rc<Module>>,
+    // Files whose import is in progress, in import order, for cycle detection
+    loading: Vec<PathBuf>,
 }
 
 impl Interpreter {
     pub fn new(evaluate_mode: bool) -> Self {
+        let mut globals = Environment::new();
+        define_globals(&mut globals);
+        let globals = Rc::new(RefCell::new(globals));
         Interpreter {
-            environment: Environment::new(),
-            evaluate_mode
+            environment: Rc::new(RefCell::new(Environment::from_enclosing(globals.clone()))),
+            globals,
+            evaluate_mode,
+            asserts_enabled: true,
+            current_file: None,
+            modules: HashMap::new(),
+            loading: Vec::new(),
         }
     }
 
+    /// Set the file the program was read from, so imports resolve relative to it.
+    pub fn set_current_file(&mut self, path: PathBuf) {
+        if let Ok(canonical) = path.canonicalize() {
+            self.loading.push(canonical);
+        }
+        self.current_file = Some(path);
+    }
+
+    /// Turn `assert` statements on or off; when off, their conditions are not evaluated.
+    pub fn set_asserts_enabled(&mut self, enabled: bool) {
+        self.asserts_enabled = enabled;
+    }
+
     /// Interpret the program by executing each statement.
     pub fn interpret(&mut self, statements: Vec<Stmt>) -> Result<(), InterpreterError> {
         for statement in statements {
@@ -28,139 +140,619 @@ impl Interpreter {
     }
 
     /// Execute a statement using the visitor pattern.
-    fn execute(&mut self, stmt: &Stmt) -> Result<(), InterpreterError> {
+    fn execute(&mut self, stmt: &Stmt) -> Result<ControlFlow, InterpreterError> {
         stmt.accept(self)  
     }
 
-    /// Evaluate an expression using the visitor pattern.
-    fn evaluate(&mut self, expr: &Expr) -> Result<Object, InterpreterError> {
-        expr.accept(self)  
+    /// Execute statements in a new scope, stopping early on `break` or `continue`.
+    fn execute_block(&mut self, statements: &[Stmt], environment: Environment) -> Result<ControlFlow, InterpreterError> {
+        let previous = std::mem::replace(&mut self.environment, Rc::new(RefCell::new(environment)));
+        let mut result = Ok(ControlFlow::Normal);
+        for statement in statements {
+            result = self.execute(statement);
+            if !matches!(result, Ok(ControlFlow::Normal)) {
+                break;
+            }
+        }
+        self.environment = previous;
+        result
     }
 
-    /// Convert an Object to its string representation.
-    fn stringify(&self, object: &Object) -> String {
-        match object {
-            Object::Number(n) => n.to_string(),
-            Object::Bool(b) => b.to_string(),
-            Object::String(s) => s.clone(),
-            Object::Nil => "nil".to_string(),
+    /// Load, run and cache the module at `path`, relative to the importing file.
+    fn import_module(&mut self, path: &Token) -> Result<Rc<Module>, InterpreterError> {
+        let relative = match &path.literal {
+            TokenLiteral::Str(s) => s.clone(),
+            _ => path.lexeme.trim_matches('"').to_string(),
+        };
+        let failed = |message: String| InterpreterError::ImportFailed {
+            path: relative.clone(),
+            message,
+            line: path.line,
+        };
+
+        let base = self
+            .current_file
+            .as_deref()
+            .and_then(Path::parent)
+            .unwrap_or_else(|| Path::new(""));
+        let resolved = base.join(&relative);
+        let canonical = resolved.canonicalize().map_err(|e| failed(e.to_string()))?;
+
+        if let Some(module) = self.modules.get(&canonical) {
+            return Ok(module.clone());
+        }
+        if let Some(start) = self.loading.iter().position(|p| *p == canonical) {
+            let cycle = self.loading[start..]
+                .iter()
+                .chain(std::iter::once(&canonical))
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(InterpreterError::ImportCycle { cycle, line: path.line });
+        }
+
+        // Static errors in the module report its own file name and line
+        let invalid = |message: String, line: usize| InterpreterError::InvalidModule {
+            path: resolved.display().to_string(),
+            message,
+            line,
+        };
+        let invalid_tokens = |error: &TokenizerError| invalid(error.message(), error.line());
+
+        let source = fs::read(&canonical).map_err(|e| failed(e.to_string()))?;
+        let source = Bytes::from(source);
+        let (tokens, errors) = tokenize(source.clone()).map_err(|e| match e.downcast_ref::<TokenizerError>() {
+            Some(error) => invalid_tokens(error),
+            None => failed(e.to_string()),
+        })?;
+        if let Some(error) = errors.first() {
+            return Err(invalid_tokens(error));
+        }
+        let statements = Parser::new(tokens, &String::from_utf8_lossy(&source), true)
+            .parse()
+            .map_err(|e| invalid(e.message, e.line))?;
+        if let Err(mut errors) = Resolver::new().resolve(&statements) {
+            let error = errors.swap_remove(0);
+            return Err(invalid(error.message, error.line));
         }
+
+        // Run the module in its own top-level scope, then restore the importer's state
+        let environment = Rc::new(RefCell::new(Environment::from_enclosing(self.globals.clone())));
+        let previous_environment = std::mem::replace(&mut self.environment, environment.clone());
+        let previous_file = self.current_file.replace(resolved.clone());
+        self.loading.push(canonical.clone());
+
+        let result = self.interpret(statements);
+
+        self.loading.pop();
+        self.current_file = previous_file;
+        self.environment = previous_environment;
+        result.map_err(|e| InterpreterError::InModule {
+            path: resolved.display().to_string(),
+            source: Box::new(e),
+        })?;
+
+        let name = Path::new(&relative)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or(relative.clone());
+        let module = Rc::new(Module { name, values: environment.borrow().values().clone() });
+        self.modules.insert(canonical, module.clone());
+        Ok(module)
     }
-}
 
-/// Implement ExprVisitor for Interpreter
-impl ExprVisitor<Result<Object, InterpreterError>> for Interpreter {
-    fn visit_literal_expr(&mut self, literal: &Literal) -> Result<Object, InterpreterError> {
+    /// Call a Lox function, binding its parameters in a scope enclosed by its closure.
+    fn call_function(&mut self, function: &Function, arguments: Vec<Object>, paren: &Token) -> Result<Object, InterpreterError> {
+        let declaration = &function.declaration;
+        if arguments.len() != declaration.params.len() {
+            return Err(InterpreterError::ArityMismatch {
+                expected: declaration.params.len(),
+                got: arguments.len(),
+                line: paren.line,
+            });
+        }
+
+        let mut environment = Environment::from_enclosing(function.closure.clone());
+        for (param, argument) in declaration.params.iter().zip(arguments) {
+            environment.define(param.lexeme.clone(), argument);
+        }
+
+        match self.execute_block(&declaration.body, environment) {
+            Ok(ControlFlow::Return(value)) => Ok(value),
+            Ok(_) => Ok(Object::Nil),
+            // Anonymous functions have no name, so errors point at where they were written
+            Err(error) => Err(InterpreterError::InFunction {
+                function: match &declaration.name {
+                    Some(name) => format!("function '{}'", name.lexeme),
+                    None => "anonymous function".to_string(),
+                },
+                line: declaration.keyword.line,
+                source: Box::new(error),
+            }),
+        }
+    }
+
+    /// The runtime value of a literal.
+    fn literal_value(&self, literal: &Literal) -> Object {
         match literal {
-            Literal::Number(n) => Ok(Object::Number(*n)),
-            Literal::String(s) => Ok(Object::String(s.clone())),
-            Literal::Bool(b) => Ok(Object::Bool(*b)),
-            Literal::Nil => Ok(Object::Nil),
+            Literal::Number(n) => Object::Number(*n),
+            Literal::Integer(n) => Object::Integer(*n),
+            Literal::String(s) => Object::String(s.clone()),
+            Literal::Bool(b) => Object::Bool(*b),
+            Literal::Nil => Object::Nil,
         }
     }
 
-    fn visit_binary_expr(&mut self, left: &Expr, operator: &Token, right: &Expr) -> Result<Object, InterpreterError> {
-        let left_val = self.evaluate(left)?;
-        let right_val = self.evaluate(right)?;
+    /// Whether a value matches a `match` pattern; literals and variants compare like `==`.
+    fn pattern_matches(&self, pattern: &Pattern, value: &Object) -> Result<bool, InterpreterError> {
+        Ok(match pattern {
+            Pattern::Literal(literal) => self.literal_value(literal) == *value,
+            Pattern::Range(low, high) => {
+                let in_range = |bound: &Literal, accept: &[Ordering]| {
+                    compare_numbers(value, &self.literal_value(bound))
+                        .is_some_and(|ordering| accept.contains(&ordering))
+                };
+                in_range(low, &[Ordering::Greater, Ordering::Equal]) && in_range(high, &[Ordering::Less])
+            }
+            Pattern::Variant { enum_name, variant } => {
+                let enumeration = self.environment.borrow().get(enum_name)?;
+                self.get_property(enumeration, variant)? == *value
+            }
+            Pattern::Wildcard => true,
+        })
+    }
 
+    /// Evaluate the arguments and call an already evaluated callee.
+    fn call_value(&mut self, callee: Object, paren: &Token, arguments: &[Expr]) -> Result<Object, InterpreterError> {
+        let arguments = arguments
+            .iter()
+            .map(|argument| self.evaluate(argument))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match callee {
+            Object::NativeFunction(native) => {
+                if arguments.len() != native.arity {
+                    return Err(InterpreterError::ArityMismatch {
+                        expected: native.arity,
+                        got: arguments.len(),
+                        line: paren.line,
+                    });
+                }
+                (native.function)(&arguments, paren.line)
+            }
+            Object::Function(function) => self.call_function(&function, arguments, paren),
+            _ => Err(InterpreterError::NotCallable { line: paren.line }),
+        }
+    }
+
+    /// Evaluate one link of an optional chain, or `None` once a `?.` has met nil and the
+    /// remaining gets, indexes and calls are skipped.
+    fn evaluate_chain(&mut self, expr: &Expr) -> Result<Option<Object>, InterpreterError> {
+        let value = match expr {
+            Expr::OptionalGet(object, name) => match self.evaluate_chain(object)? {
+                None | Some(Object::Nil) => None,
+                Some(object) => Some(self.get_property(object, name)?),
+            },
+            Expr::Get(object, name) => match self.evaluate_chain(object)? {
+                None => None,
+                Some(object) => Some(self.get_property(object, name)?),
+            },
+            Expr::Index(object, bracket, index) => match self.evaluate_chain(object)? {
+                None => None,
+                Some(object) => {
+                    let index = self.evaluate(index)?;
+                    Some(self.get_index(&object, &index, bracket)?)
+                }
+            },
+            Expr::Call(callee, paren, arguments) => match self.evaluate_chain(callee)? {
+                None => None,
+                Some(callee) => Some(self.call_value(callee, paren, arguments)?),
+            },
+            _ => Some(self.evaluate(expr)?),
+        };
+        Ok(value)
+    }
+
+    /// Look up a property of a map (by string key), module (by binding name) or enum (by variant).
+    fn get_property(&self, object: Object, name: &Token) -> Result<Object, InterpreterError> {
+        let value = match object {
+            Object::Map(entries) => entries.borrow().get(&HashKey::String(name.lexeme.clone())).cloned(),
+            Object::Module(module) => module.values.get(&name.lexeme).cloned(),
+            Object::Enum(enumeration) => {
+                return match enumeration.variant(&name.lexeme) {
+                    Some(variant) => Ok(Object::Variant(variant.clone())),
+                    None => Err(InterpreterError::UndefinedVariant {
+                        enum_name: enumeration.name.clone(),
+                        name: name.lexeme.clone(),
+                        line: name.line,
+                    }),
+                };
+            }
+            _ => return Err(InterpreterError::NoProperties { line: name.line }),
+        };
+        value.ok_or_else(|| InterpreterError::UndefinedProperty {
+            name: name.lexeme.clone(),
+            line: name.line,
+        })
+    }
+
+    /// Run a loop body once per item, each time in a fresh scope binding `name`.
+    fn run_for_in(&mut self, name: &Token, body: &Stmt, items: impl Iterator<Item = Object>) -> Result<ControlFlow, InterpreterError> {
+        for item in items {
+            let mut environment = Environment::from_enclosing(self.environment.clone());
+            environment.define(name.lexeme.clone(), item);
+            match self.execute_block(std::slice::from_ref(body), environment)? {
+                ControlFlow::Break => break,
+                ControlFlow::Return(value) => return Ok(ControlFlow::Return(value)),
+                ControlFlow::Normal | ControlFlow::Continue => {}
+            }
+        }
+        Ok(ControlFlow::Normal)
+    }
+
+    /// Take one element per name from a list, in order.
+    fn unpack_list(&self, value: &Object, names: &[Token], bracket: &Token) -> Result<Vec<Object>, InterpreterError> {
+        let Object::List(elements) = value else {
+            return Err(InterpreterError::NotDestructurable { expected: "lists".to_string(), line: bracket.line });
+        };
+        let elements = elements.borrow();
+        names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                elements.get(index).cloned().ok_or_else(|| InterpreterError::MissingElement {
+                    name: name.lexeme.clone(),
+                    index,
+                    line: name.line,
+                })
+            })
+            .collect()
+    }
+
+    /// Take each name's value from a map's string keys or a module's bindings.
+    fn unpack_fields(&self, value: &Object, names: &[Token], brace: &Token) -> Result<Vec<Object>, InterpreterError> {
+        if !matches!(value, Object::Map(_) | Object::Module(_)) {
+            return Err(InterpreterError::NotDestructurable { expected: "maps and modules".to_string(), line: brace.line });
+        }
+        names
+            .iter()
+            .map(|name| {
+                self.get_property(value.clone(), name).map_err(|_| InterpreterError::MissingField {
+                    name: name.lexeme.clone(),
+                    line: name.line,
+                })
+            })
+            .collect()
+    }
+
+    fn make_function(&self, declaration: &Rc<FunctionDecl>) -> Object {
+        Object::Function(Rc::new(Function {
+            declaration: declaration.clone(),
+            closure: self.environment.clone(),
+        }))
+    }
+
+    /// Evaluate an expression using the visitor pattern.
+    fn evaluate(&mut self, expr: &Expr) -> Result<Object, InterpreterError> {
+        expr.accept(self)  
+    }
+
+    /// Convert an Object to its string representation.
+    fn stringify(&self, object: &Object) -> String {
+        object.to_string()
+    }
+
+    /// Apply a binary operator to two evaluated operands.
+    fn binary_op(&self, left_val: Object, operator: &Token, right_val: Object) -> Result<Object, InterpreterError> {
         match operator.token_type {
+            // Handle set union, intersection and difference
+            TokenType::Pipe | TokenType::Ampersand | TokenType::Minus
+                if matches!((&left_val, &right_val), (Object::Set(_), Object::Set(_))) =>
+            {
+                Ok(self.set_algebra(&left_val, operator, &right_val))
+            }
+
             // Handle addition and string concatenation
             TokenType::Plus => {
-                if let (Object::Number(left_num), Object::Number(right_num)) = (left_val.clone(), right_val.clone()) {
-                    Ok(Object::Number(left_num + right_num))
-                } else if let (Object::String(left_str), Object::String(right_str)) = (left_val, right_val) {
+                if let (Object::String(left_str), Object::String(right_str)) = (&left_val, &right_val) {
                     Ok(Object::String(format!("{}{}", left_str, right_str))) // Handle string concatenation
                 } else {
-                    Err(InterpreterError::InvalidBinaryOperands { line: operator.line })
+                    self.arithmetic(&left_val, operator, &right_val)
                 }
             }
 
-            // Handle subtraction
-            TokenType::Minus => {
-                if let (Object::Number(left_num), Object::Number(right_num)) = (left_val, right_val) {
-                    Ok(Object::Number(left_num - right_num))
-                } else {
-                    Err(InterpreterError::InvalidBinaryOperands { line: operator.line })
-                }
+            // Handle the other arithmetic operators
+            TokenType::Minus | TokenType::Star | TokenType::Slash | TokenType::Tilde_Slash
+            | TokenType::Percent | TokenType::Star_Star => self.arithmetic(&left_val, operator, &right_val),
+
+            // Handle bitwise and shift operators
+            TokenType::Ampersand | TokenType::Pipe | TokenType::Caret
+            | TokenType::Less_Less | TokenType::Greater_Greater => {
+                self.bitwise(&left_val, operator, &right_val)
             }
 
-            // Handle multiplication
-            TokenType::Star => {
-                if let (Object::Number(left_num), Object::Number(right_num)) = (left_val, right_val) {
-                    Ok(Object::Number(left_num * right_num))
-                } else {
-                    Err(InterpreterError::InvalidBinaryOperands { line: operator.line })
+            // Handle equality and inequality
+            TokenType::Equal_Equal => Ok(Object::Bool(left_val == right_val)),
+            TokenType::Bang_Equal => Ok(Object::Bool(left_val != right_val)),
+
+            // Handle comparisons; NaN compares false against everything
+            TokenType::Greater | TokenType::Greater_Equal | TokenType::Less | TokenType::Less_Equal => {
+                if left_val.as_number().is_err() || right_val.as_number().is_err() {
+                    return Err(InterpreterError::InvalidBinaryOperands { line: operator.line });
                 }
+                let ordering = compare_numbers(&left_val, &right_val);
+                Ok(Object::Bool(match operator.token_type {
+                    TokenType::Greater => ordering == Some(Ordering::Greater),
+                    TokenType::Greater_Equal => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+                    TokenType::Less => ordering == Some(Ordering::Less),
+                    _ => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                }))
             }
 
-            // Handle division
-            TokenType::Slash => {
-                if let (Object::Number(left_num), Object::Number(right_num)) = (left_val, right_val) {
-                    if right_num == 0.0 {
-                        Err(InterpreterError::DivisionByZero { line: operator.line })
-                    } else {
-                        Ok(Object::Number(left_num / right_num))
-                    }
-                } else {
-                    Err(InterpreterError::InvalidBinaryOperands { line: operator.line })
-                }
+            _ => Ok(Object::Nil),
+        }
+    }
+
+    /// Apply an arithmetic operator after promoting the operands: two integers stay
+    /// integers (except under `/`), and a float on either side makes both floats.
+    fn arithmetic(&self, left: &Object, operator: &Token, right: &Object) -> Result<Object, InterpreterError> {
+        let line = operator.line;
+        let pair = NumericPair::of(left, right).ok_or(match operator.token_type {
+            TokenType::Plus | TokenType::Minus | TokenType::Star | TokenType::Slash => {
+                InterpreterError::InvalidBinaryOperands { line }
             }
+            _ => InterpreterError::InvalidNumericOperands { line },
+        })?;
 
-            // Handle equality and inequality
-            TokenType::Equal_Equal => Ok(Object::Bool(match (&left_val, &right_val) {
-                (Object::String(l), Object::String(r)) => l == r,  
-                (Object::Number(l), Object::Number(r)) => l == r,  
-                (Object::Bool(l), Object::Bool(r)) => l == r,      
-                (Object::Nil, Object::Nil) => true,               
-                _ => false,                                        
-            })),
-            TokenType::Bang_Equal => Ok(Object::Bool(match (&left_val, &right_val) {
-                (Object::String(l), Object::String(r)) => l != r, 
-                (Object::Number(l), Object::Number(r)) => l != r,  
-                (Object::Bool(l), Object::Bool(r)) => l != r,      
-                (Object::Nil, Object::Nil) => false,               
-                _ => true,                                         
-            })),
-            // Handle greater than
-            TokenType::Greater => {
-                if let (Object::Number(left_num), Object::Number(right_num)) = (left_val, right_val) {
-                    Ok(Object::Bool(left_num > right_num))
-                } else {
-                    Err(InterpreterError::InvalidBinaryOperands { line: operator.line })
+        match pair {
+            NumericPair::Integers(left, right) => self.integer_arithmetic(left, operator, right),
+            NumericPair::Floats(left, right) => self.float_arithmetic(left, operator, right),
+        }
+    }
+
+    /// Integer arithmetic, raising an error instead of wrapping on overflow.
+    fn integer_arithmetic(&self, left: i64, operator: &Token, right: i64) -> Result<Object, InterpreterError> {
+        let line = operator.line;
+        let result = match operator.token_type {
+            TokenType::Plus => left.checked_add(right),
+            TokenType::Minus => left.checked_sub(right),
+            TokenType::Star => left.checked_mul(right),
+            // `/` always divides exactly, so `7 / 2` is 3.5; `~/` is the integer division
+            TokenType::Slash => return self.float_arithmetic(left as f64, operator, right as f64),
+            TokenType::Tilde_Slash => {
+                if right == 0 {
+                    return Err(InterpreterError::DivisionByZero { line });
                 }
+                // Round towards negative infinity, like the float version
+                left.checked_div(right).map(|quotient| {
+                    if left % right != 0 && (left < 0) != (right < 0) { quotient - 1 } else { quotient }
+                })
             }
-
-            // Handle greater than or equal
-            TokenType::Greater_Equal => {
-                if let (Object::Number(left_num), Object::Number(right_num)) = (left_val, right_val) {
-                    Ok(Object::Bool(left_num >= right_num))
-                } else {
-                    Err(InterpreterError::InvalidBinaryOperands { line: operator.line })
+            // The result takes the sign of the divisor, so -7 % 3 is 2
+            TokenType::Percent => {
+                if right == 0 {
+                    return Err(InterpreterError::ModuloByZero { line });
                 }
+                let remainder = left.wrapping_rem(right);
+                Some(if remainder != 0 && (remainder < 0) != (right < 0) { remainder + right } else { remainder })
             }
+            // A negative exponent can't give an integer, so fall back to floats
+            TokenType::Star_Star if right < 0 => return self.float_arithmetic(left as f64, operator, right as f64),
+            TokenType::Star_Star => u32::try_from(right).ok().and_then(|exponent| left.checked_pow(exponent)),
+            _ => unreachable!("not an arithmetic operator: {}", operator.lexeme),
+        };
+        result.map(Object::Integer).ok_or(InterpreterError::IntegerOverflow {
+            operator: operator.lexeme.clone(),
+            line,
+        })
+    }
 
-            // Handle less than
-            TokenType::Less => {
-                if let (Object::Number(left_num), Object::Number(right_num)) = (left_val, right_val) {
-                    Ok(Object::Bool(left_num < right_num))
-                } else {
-                    Err(InterpreterError::InvalidBinaryOperands { line: operator.line })
+    /// Float arithmetic, where only division and modulo by zero are errors.
+    fn float_arithmetic(&self, left: f64, operator: &Token, right: f64) -> Result<Object, InterpreterError> {
+        let line = operator.line;
+        let result = match operator.token_type {
+            TokenType::Plus => left + right,
+            TokenType::Minus => left - right,
+            TokenType::Star => left * right,
+            TokenType::Slash | TokenType::Tilde_Slash if right == 0.0 => {
+                return Err(InterpreterError::DivisionByZero { line });
+            }
+            TokenType::Slash => left / right,
+            TokenType::Tilde_Slash => (left / right).floor(),
+            // The result takes the sign of the divisor, so -7 % 3 is 2
+            TokenType::Percent => {
+                if right == 0.0 {
+                    return Err(InterpreterError::ModuloByZero { line });
                 }
+                let remainder = left % right;
+                if remainder != 0.0 && (remainder < 0.0) != (right < 0.0) { remainder + right } else { remainder }
             }
+            TokenType::Star_Star => left.powf(right),
+            _ => unreachable!("not an arithmetic operator: {}", operator.lexeme),
+        };
+        Ok(Object::Number(result))
+    }
 
-            // Handle less than or equal
-            TokenType::Less_Equal => {
-                if let (Object::Number(left_num), Object::Number(right_num)) = (left_val, right_val) {
-                    Ok(Object::Bool(left_num <= right_num))
+    /// Convert an operand of a bitwise operator to an integer. Floats are accepted when
+    /// integral and within the range where `f64` represents every integer exactly.
+    fn integer_operand(&self, value: &Object, operator: &Token) -> Result<i64, InterpreterError> {
+        const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+        match value {
+            Object::Integer(n) => Ok(*n),
+            Object::Number(n) if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER => Ok(*n as i64),
+            _ => Err(InterpreterError::NonIntegerOperand {
+                operator: operator.lexeme.clone(),
+                line: operator.line,
+            }),
+        }
+    }
+
+    /// Combine two sets into a new one, keeping the left operand's order first.
+    fn set_algebra(&self, left: &Object, operator: &Token, right: &Object) -> Object {
+        let (Object::Set(left), Object::Set(right)) = (left, right) else {
+            unreachable!("set operator applied to non-sets: {}", operator.lexeme)
+        };
+        let (left, right) = (left.borrow(), right.borrow());
+        let elements = match operator.token_type {
+            TokenType::Pipe => left.union(&right).cloned().collect(),
+            TokenType::Ampersand => left.intersection(&right).cloned().collect(),
+            _ => left.difference(&right).cloned().collect(),
+        };
+        Object::set(elements)
+    }
+
+    /// Evaluate a bitwise or shift operator on two integral numbers.
+    fn bitwise(&self, left: &Object, operator: &Token, right: &Object) -> Result<Object, InterpreterError> {
+        let left = self.integer_operand(left, operator)?;
+        let right = self.integer_operand(right, operator)?;
+
+        let result = match operator.token_type {
+            TokenType::Ampersand => left & right,
+            TokenType::Pipe => left | right,
+            TokenType::Caret => left ^ right,
+            TokenType::Less_Less | TokenType::Greater_Greater => {
+                if !(0..64).contains(&right) {
+                    return Err(InterpreterError::InvalidShift { line: operator.line });
+                }
+                if operator.token_type == TokenType::Less_Less {
+                    // A left shift overflows when shifting back doesn't recover the operand
+                    left
+                        .checked_shl(right as u32)
+                        .filter(|shifted| shifted >> right == left)
+                        .ok_or(InterpreterError::IntegerOverflow {
+                            operator: operator.lexeme.clone(),
+                            line: operator.line,
+                        })?
                 } else {
-                    Err(InterpreterError::InvalidBinaryOperands { line: operator.line })
+                    left >> right
                 }
             }
+            _ => unreachable!("not a bitwise operator: {}", operator.lexeme),
+        };
+        Ok(Object::Integer(result))
+    }
 
-            _ => Ok(Object::Nil),
+    /// Convert a value to a map key, rejecting unhashable values.
+    fn map_key(&self, key: &Object, token: &Token) -> Result<HashKey, InterpreterError> {
+        key.to_key().ok_or(InterpreterError::UnhashableKey { line: token.line })
+    }
+
+    /// Read `object[index]` from a list or map.
+    fn get_index(&self, object: &Object, index: &Object, bracket: &Token) -> Result<Object, InterpreterError> {
+        match object {
+            Object::List(elements) => {
+                let elements = elements.borrow();
+                let index = self.list_index(index, elements.len(), bracket)?;
+                Ok(elements[index].clone())
+            }
+            Object::Map(entries) => {
+                let key = self.map_key(index, bracket)?;
+                entries.borrow().get(&key).cloned().ok_or_else(|| InterpreterError::UndefinedKey {
+                    key: key.to_object().to_string(),
+                    line: bracket.line,
+                })
+            }
+            _ => Err(InterpreterError::NotIndexable { line: bracket.line }),
+        }
+    }
+
+    /// Write `object[index] = value` into a list or map.
+    fn set_index(&self, object: &Object, index: &Object, value: Object, bracket: &Token) -> Result<(), InterpreterError> {
+        match object {
+            Object::List(elements) => {
+                let mut elements = elements.borrow_mut();
+                let index = self.list_index(index, elements.len(), bracket)?;
+                elements[index] = value;
+                Ok(())
+            }
+            Object::Map(entries) => {
+                let key = self.map_key(index, bracket)?;
+                entries.borrow_mut().insert(key, value);
+                Ok(())
+            }
+            _ => Err(InterpreterError::NotIndexable { line: bracket.line }),
+        }
+    }
+
+    /// Read-modify-write an assignment target. The target's sub-expressions are evaluated
+    /// and its current value is read exactly once. Returns the old and new values.
+    fn update_target(
+        &mut self,
+        target: &Expr,
+        modify: impl FnOnce(&mut Self, Object) -> Result<Object, InterpreterError>,
+    ) -> Result<(Object, Object), InterpreterError> {
+        match target {
+            Expr::Variable(name) => {
+                let old = self.environment.borrow().get(name)?;
+                let new = modify(self, old.clone())?;
+                self.environment.borrow_mut().assign(name, new.clone())?;
+                Ok((old, new))
+            }
+            Expr::Index(object, bracket, index) => {
+                let object = self.evaluate(object)?;
+                let index = self.evaluate(index)?;
+                let old = self.get_index(&object, &index, bracket)?;
+                let new = modify(self, old.clone())?;
+                self.set_index(&object, &index, new.clone(), bracket)?;
+                Ok((old, new))
+            }
+            _ => unreachable!("the parser only produces variable and index assignment targets"),
         }
     }
 
+    /// Add `delta` to a numeric target for `++` and `--`.
+    fn increment(&mut self, target: &Expr, operator: &Token) -> Result<(Object, Object), InterpreterError> {
+        let delta = if operator.token_type == TokenType::Plus_Plus { 1 } else { -1 };
+        self.update_target(target, |_, old| match old {
+            Object::Integer(n) => n.checked_add(delta).map(Object::Integer).ok_or(InterpreterError::IntegerOverflow {
+                operator: operator.lexeme.clone(),
+                line: operator.line,
+            }),
+            Object::Number(n) => Ok(Object::Number(n + delta as f64)),
+            _ => Err(InterpreterError::InvalidUnaryOperand { line: operator.line }),
+        })
+    }
+
+    /// Validate a list index against the list length.
+    fn list_index(&self, index: &Object, len: usize, bracket: &Token) -> Result<usize, InterpreterError> {
+        let index = match index {
+            Object::Integer(n) => *n,
+            Object::Number(n) => float_to_integer(*n).ok_or(InterpreterError::InvalidIndex { line: bracket.line })?,
+            _ => return Err(InterpreterError::InvalidIndex { line: bracket.line }),
+        };
+        if index < 0 {
+            return Err(InterpreterError::NegativeIndex { index, line: bracket.line });
+        }
+
+        let index = index as usize;
+        if index >= len {
+            return Err(InterpreterError::IndexOutOfBounds { index, len, line: bracket.line });
+        }
+        Ok(index)
+    }
+}
+
+/// Implement ExprVisitor for Interpreter
+impl ExprVisitor<Result<Object, InterpreterError>> for Interpreter {
+    fn visit_literal_expr(&mut self, literal: &Literal) -> Result<Object, InterpreterError> {
+        Ok(self.literal_value(literal))
+    }
+
+    fn visit_binary_expr(&mut self, left: &Expr, operator: &Token, right: &Expr) -> Result<Object, InterpreterError> {
+        let left_val = self.evaluate(left)?;
+
+        // `and` and `or` short-circuit, yielding whichever operand decided the result
+        match operator.token_type {
+            TokenType::Or if left_val.is_truthy() => return Ok(left_val),
+            TokenType::And if !left_val.is_truthy() => return Ok(left_val),
+            TokenType::Or | TokenType::And => return self.evaluate(right),
+            _ => {}
+        }
+
+        let right_val = self.evaluate(right)?;
+        self.binary_op(left_val, operator, right_val)
+    }
+
     fn visit_grouping_expr(&mut self, expr: &Expr) -> Result<Object, InterpreterError> {
         self.evaluate(expr)
     }
@@ -169,64 +761,377 @@ impl ExprVisitor<Result<Object, InterpreterError>> for Interpreter {
         let right_val = self.evaluate(right)?;
         match operator.token_type {
             TokenType::Minus => {
-                if let Object::Number(val) = right_val {
-                    Ok(Object::Number(-val))
-                } else {
-                    Err(InterpreterError::InvalidUnaryOperand { line: operator.line })
+                match right_val {
+                    Object::Number(val) => Ok(Object::Number(-val)),
+                    Object::Integer(val) => val.checked_neg().map(Object::Integer).ok_or(
+                        InterpreterError::IntegerOverflow { operator: operator.lexeme.clone(), line: operator.line },
+                    ),
+                    _ => Err(InterpreterError::InvalidUnaryOperand { line: operator.line }),
                 }
             }
             TokenType::Bang => Ok(Object::Bool(!right_val.is_truthy())),  // Negation
+            TokenType::Tilde => Ok(Object::Integer(!self.integer_operand(&right_val, operator)?)),
             _ => Ok(Object::Nil),
         }
     }
     
     fn visit_variable_expr(&mut self, token: &Token) -> Result<Object, InterpreterError> {
         self.environment
+            .borrow()
             .get(token)
             .map_err(|_| InterpreterError::UndefinedVariable { name: token.lexeme.clone(), line: token.line })
     }
 
     fn visit_assign_expr(&mut self, name: &Token, value: &Expr) -> Result<Object, InterpreterError> {
         let value = self.evaluate(value)?;
-        self.environment.assign(name, value.clone())
-            .map_err(|_| InterpreterError::UndefinedVariable { name: name.lexeme.clone(), line: name.line })?;
+        self.environment.borrow_mut().assign(name, value.clone())?;
+        Ok(value)
+    }
+
+    fn visit_interpolation_expr(&mut self, parts: &[Expr]) -> Result<Object, InterpreterError> {
+        let mut result = String::new();
+        for part in parts {
+            let value = self.evaluate(part)?;
+            result.push_str(&self.stringify(&value));
+        }
+        Ok(Object::String(result))
+    }
+
+    fn visit_list_expr(&mut self, elements: &[Expr]) -> Result<Object, InterpreterError> {
+        let values = elements
+            .iter()
+            .map(|element| self.evaluate(element))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Object::list(values))
+    }
+
+    fn visit_map_expr(&mut self, brace: &Token, entries: &[(Expr, Expr)]) -> Result<Object, InterpreterError> {
+        let mut map = IndexMap::new();
+        for (key, value) in entries {
+            let key = self.evaluate(key)?;
+            let key = self.map_key(&key, brace)?;
+            let value = self.evaluate(value)?;
+            map.insert(key, value);
+        }
+        Ok(Object::map(map))
+    }
+
+    fn visit_index_expr(&mut self, object: &Expr, bracket: &Token, index: &Expr) -> Result<Object, InterpreterError> {
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+        self.get_index(&object, &index, bracket)
+    }
+
+    fn visit_index_assign_expr(&mut self, object: &Expr, bracket: &Token, index: &Expr, value: &Expr) -> Result<Object, InterpreterError> {
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+        let value = self.evaluate(value)?;
+        self.set_index(&object, &index, value.clone(), bracket)?;
         Ok(value)
     }
+
+    fn visit_call_expr(&mut self, callee: &Expr, paren: &Token, arguments: &[Expr]) -> Result<Object, InterpreterError> {
+        let callee = self.evaluate(callee)?;
+        self.call_value(callee, paren, arguments)
+    }
+
+    fn visit_get_expr(&mut self, object: &Expr, name: &Token) -> Result<Object, InterpreterError> {
+        let object = self.evaluate(object)?;
+        self.get_property(object, name)
+    }
+
+    fn visit_conditional_expr(&mut self, condition: &Expr, then_branch: &Expr, else_branch: &Expr) -> Result<Object, InterpreterError> {
+        if self.evaluate(condition)?.is_truthy() {
+            self.evaluate(then_branch)
+        } else {
+            self.evaluate(else_branch)
+        }
+    }
+
+    fn visit_comma_expr(&mut self, left: &Expr, right: &Expr) -> Result<Object, InterpreterError> {
+        self.evaluate(left)?;
+        self.evaluate(right)
+    }
+
+    fn visit_compound_assign_expr(&mut self, target: &Expr, operator: &Token, value: &Expr) -> Result<Object, InterpreterError> {
+        // `x += y` applies the same rules as `x + y`
+        let binary_type = match operator.token_type {
+            TokenType::Plus_Equal => TokenType::Plus,
+            TokenType::Minus_Equal => TokenType::Minus,
+            TokenType::Star_Equal => TokenType::Star,
+            _ => TokenType::Slash,
+        };
+        let binary_operator = Token::new(
+            binary_type,
+            operator.lexeme.trim_end_matches('=').to_string(),
+            TokenLiteral::Nil,
+            operator.line,
+        );
+
+        let (_, new) = self.update_target(target, |interpreter, old| {
+            let value = interpreter.evaluate(value)?;
+            interpreter.binary_op(old, &binary_operator, value)
+        })?;
+        Ok(new)
+    }
+
+    fn visit_prefix_update_expr(&mut self, operator: &Token, target: &Expr) -> Result<Object, InterpreterError> {
+        let (_, new) = self.increment(target, operator)?;
+        Ok(new)
+    }
+
+    fn visit_postfix_update_expr(&mut self, target: &Expr, operator: &Token) -> Result<Object, InterpreterError> {
+        let (old, _) = self.increment(target, operator)?;
+        Ok(old)
+    }
+
+    fn visit_lambda_expr(&mut self, function: &Rc<FunctionDecl>) -> Result<Object, InterpreterError> {
+        Ok(self.make_function(function))
+    }
+
+    fn visit_nil_coalesce_expr(&mut self, left: &Expr, _operator: &Token, right: &Expr) -> Result<Object, InterpreterError> {
+        // Unlike `or`, only nil falls through; `false` is kept
+        match self.evaluate(left)? {
+            Object::Nil => self.evaluate(right),
+            value => Ok(value),
+        }
+    }
+
+    fn visit_list_assign_expr(&mut self, names: &[Token], equals: &Token, value: &Expr) -> Result<Object, InterpreterError> {
+        // The whole right side is evaluated before any target changes, so `[a, b] = [b, a]` swaps
+        let value = self.evaluate(value)?;
+        let values = self.unpack_list(&value, names, equals)?;
+        for (name, element) in names.iter().zip(values) {
+            self.environment.borrow_mut().assign(name, element)?;
+        }
+        Ok(value)
+    }
+
+    fn visit_optional_get_expr(&mut self, object: &Expr, name: &Token) -> Result<Object, InterpreterError> {
+        match self.evaluate(object)? {
+            Object::Nil => Ok(Object::Nil),
+            object => self.get_property(object, name),
+        }
+    }
+
+    fn visit_optional_chain_expr(&mut self, chain: &Expr) -> Result<Object, InterpreterError> {
+        Ok(self.evaluate_chain(chain)?.unwrap_or(Object::Nil))
+    }
 }
 
 /// Implement StmtVisitor for Interpreter
-impl StmtVisitor<Result<(), InterpreterError>> for Interpreter {
-    fn visit_print_stmt(&mut self, expr: &Expr) -> Result<(), InterpreterError> {
+impl StmtVisitor<Result<ControlFlow, InterpreterError>> for Interpreter {
+    fn visit_print_stmt(&mut self, expr: &Expr) -> Result<ControlFlow, InterpreterError> {
         let value = self.evaluate(expr)?;
         println!("{}", self.stringify(&value));
-        Ok(())
+        Ok(ControlFlow::Normal)
     }
 
-    fn visit_var_stmt(&mut self, name: &Token, initializer: Option<&Expr>) -> Result<(), InterpreterError> {
+    fn visit_var_stmt(&mut self, binding: &Binding, _annotation: Option<&Token>, initializer: Option<&Expr>) -> Result<ControlFlow, InterpreterError> {
         let value = if let Some(expr) = initializer {
             self.evaluate(expr)?
         } else {
             Object::Nil
         };
-        self.environment.define(name.lexeme.clone(), value);
-        Ok(())
+        let values = match binding {
+            Binding::Name(_) => vec![value],
+            Binding::List { bracket, names } => self.unpack_list(&value, names, bracket)?,
+            Binding::Map { brace, names } => self.unpack_fields(&value, names, brace)?,
+        };
+        let mut environment = self.environment.borrow_mut();
+        for (name, value) in binding.names().iter().zip(values) {
+            environment.define(name.lexeme.clone(), value);
+        }
+        Ok(ControlFlow::Normal)
+    }
+
+    fn visit_const_stmt(&mut self, name: &Token, _annotation: Option<&Token>, initializer: &Expr) -> Result<ControlFlow, InterpreterError> {
+        let value = self.evaluate(initializer)?;
+        self.environment.borrow_mut().define_constant(name.lexeme.clone(), value);
+        Ok(ControlFlow::Normal)
     }
 
-    fn visit_expression_stmt(&mut self, expr: &Expr) -> Result<(), InterpreterError> {
+    fn visit_expression_stmt(&mut self, expr: &Expr) -> Result<ControlFlow, InterpreterError> {
         let value = self.evaluate(expr)?;
 
         if self.evaluate_mode {
             println!("{}", self.stringify(&value));
         }
 
-        Ok(())
+        Ok(ControlFlow::Normal)
     }
 
-    fn visit_block_stmt(&mut self, statements: &[Stmt]) -> Result<(), InterpreterError> {
-        let previous_env = self.environment.clone();
-        self.environment = Environment::from_enclosing(previous_env.clone());
-        let result = statements.iter().try_for_each(|stmt| self.execute(stmt));
-        self.environment = previous_env;
+    fn visit_block_stmt(&mut self, statements: &[Stmt]) -> Result<ControlFlow, InterpreterError> {
+        let environment = Environment::from_enclosing(self.environment.clone());
+        self.execute_block(statements, environment)
+    }
+
+    fn visit_if_stmt(&mut self, condition: &Expr, then_branch: &Stmt, else_branch: Option<&Stmt>) -> Result<ControlFlow, InterpreterError> {
+        if self.evaluate(condition)?.is_truthy() {
+            self.execute(then_branch)
+        } else if let Some(else_branch) = else_branch {
+            self.execute(else_branch)
+        } else {
+            Ok(ControlFlow::Normal)
+        }
+    }
+
+    fn visit_while_stmt(&mut self, condition: &Expr, body: &Stmt, increment: Option<&Expr>) -> Result<ControlFlow, InterpreterError> {
+        while self.evaluate(condition)?.is_truthy() {
+            match self.execute(body)? {
+                ControlFlow::Break => break,
+                ControlFlow::Return(value) => return Ok(ControlFlow::Return(value)),
+                ControlFlow::Normal | ControlFlow::Continue => {}
+            }
+            // Runs after `continue` too, so desugared `for` loops still advance
+            if let Some(increment) = increment {
+                self.evaluate(increment)?;
+            }
+        }
+        Ok(ControlFlow::Normal)
+    }
+
+    fn visit_for_in_stmt(&mut self, name: &Token, keyword: &Token, iterable: &Expr, end: Option<&Expr>, body: &Stmt) -> Result<ControlFlow, InterpreterError> {
+        let value = self.evaluate(iterable)?;
+        let Some(end) = end else {
+            let items = value.items().ok_or(InterpreterError::NotIterable { line: keyword.line })?;
+            return self.run_for_in(name, body, items.into_iter());
+        };
+
+        // Ranges are produced lazily, so `0..1000000` doesn't build a list
+        match NumericPair::of(&value, &self.evaluate(end)?) {
+            Some(NumericPair::Integers(start, end)) => self.run_for_in(name, body, (start..end).map(Object::Integer)),
+            Some(NumericPair::Floats(start, end)) => {
+                // Adding 1.0 stops changing large floats, so bound the number of steps up front
+                let length = (end - start).ceil().max(0.0);
+                if !(start.is_finite() && end.is_finite()) || length > MAX_RANGE_LENGTH as f64 {
+                    return Err(InterpreterError::FloatRangeTooLong {
+                        limit: MAX_RANGE_LENGTH,
+                        line: keyword.line,
+                    });
+                }
+                let values = (0..length as u64).map(move |i| start + i as f64).take_while(move |value| *value < end);
+                self.run_for_in(name, body, values.map(Object::Number))
+            }
+            None => Err(InterpreterError::InvalidRange { line: keyword.line }),
+        }
+    }
+
+    fn visit_break_stmt(&mut self, _keyword: &Token) -> Result<ControlFlow, InterpreterError> {
+        Ok(ControlFlow::Break)
+    }
+
+    fn visit_continue_stmt(&mut self, _keyword: &Token) -> Result<ControlFlow, InterpreterError> {
+        Ok(ControlFlow::Continue)
+    }
+
+    fn visit_throw_stmt(&mut self, keyword: &Token, value: &Expr) -> Result<ControlFlow, InterpreterError> {
+        let value = self.evaluate(value)?;
+        Err(InterpreterError::Thrown { value, line: keyword.line })
+    }
+
+    fn visit_assert_stmt(&mut self, keyword: &Token, condition: &Expr, source: &str, message: Option<&Expr>) -> Result<ControlFlow, InterpreterError> {
+        if !self.asserts_enabled {
+            return Ok(ControlFlow::Normal);
+        }
+
+        // For a top-level comparison, keep the operands so the failure can show them
+        let comparison = match condition {
+            Expr::Binary(left, operator, right) => {
+                comparison_negation(&operator.token_type).map(|negation| (left, operator, right, negation))
+            }
+            _ => None,
+        };
+        let (passed, operands) = match comparison {
+            Some((left, operator, right, negation)) => {
+                let left = self.evaluate(left)?;
+                let right = self.evaluate(right)?;
+                let passed = self.binary_op(left.clone(), operator, right.clone())?.is_truthy();
+                (passed, Some(format!("{} {} {}", left.to_repr(), negation, right.to_repr())))
+            }
+            None => (self.evaluate(condition)?.is_truthy(), None),
+        };
+        if passed {
+            return Ok(ControlFlow::Normal);
+        }
+
+        let message = match message {
+            Some(message) => Some(self.evaluate(message)?.to_string()),
+            None => None,
+        };
+        Err(InterpreterError::AssertionFailed {
+            condition: source.to_string(),
+            operands,
+            message,
+            line: keyword.line,
+        })
+    }
+
+    fn visit_import_stmt(&mut self, _keyword: &Token, path: &Token, alias: &Token) -> Result<ControlFlow, InterpreterError> {
+        let module = self.import_module(path)?;
+        self.environment.borrow_mut().define(alias.lexeme.clone(), Object::Module(module));
+        Ok(ControlFlow::Normal)
+    }
+
+    fn visit_function_stmt(&mut self, function: &Rc<FunctionDecl>) -> Result<ControlFlow, InterpreterError> {
+        let value = self.make_function(function);
+        if let Some(name) = &function.name {
+            self.environment.borrow_mut().define(name.lexeme.clone(), value);
+        }
+        Ok(ControlFlow::Normal)
+    }
+
+    fn visit_return_stmt(&mut self, _keyword: &Token, value: Option<&Expr>) -> Result<ControlFlow, InterpreterError> {
+        let value = match value {
+            Some(value) => self.evaluate(value)?,
+            None => Object::Nil,
+        };
+        Ok(ControlFlow::Return(value))
+    }
+
+    fn visit_match_stmt(&mut self, _keyword: &Token, subject: &Expr, arms: &[MatchArm]) -> Result<ControlFlow, InterpreterError> {
+        let value = self.evaluate(subject)?;
+        for arm in arms {
+            for pattern in &arm.patterns {
+                if self.pattern_matches(pattern, &value)? {
+                    return self.execute(&arm.body);
+                }
+            }
+        }
+        Ok(ControlFlow::Normal)
+    }
+
+    fn visit_enum_stmt(&mut self, name: &Token, variants: &[Token]) -> Result<ControlFlow, InterpreterError> {
+        let variant_names = variants.iter().map(|variant| variant.lexeme.clone()).collect();
+        let enumeration = Enum::new(name.lexeme.clone(), variant_names);
+        self.environment
+            .borrow_mut()
+            .define_constant(name.lexeme.clone(), Object::Enum(Rc::new(enumeration)));
+        Ok(ControlFlow::Normal)
+    }
+
+    fn visit_try_stmt(&mut self, body: &[Stmt], catch: Option<&CatchClause>, finally: Option<&[Stmt]>) -> Result<ControlFlow, InterpreterError> {
+        let environment = Environment::from_enclosing(self.environment.clone());
+        let mut result = self.execute_block(body, environment);
+
+        // Failed asserts and import errors skip the catch clause and propagate after `finally`
+        if let (Err(error), Some(catch)) = (&result, catch) {
+            if error.is_catchable() {
+                let mut environment = Environment::from_enclosing(self.environment.clone());
+                environment.define(catch.name.lexeme.clone(), error.to_value());
+                result = self.execute_block(&catch.body, environment);
+            }
+        }
+
+        if let Some(finally) = finally {
+            // An error or jump out of the finally block replaces the pending outcome
+            let environment = Environment::from_enclosing(self.environment.clone());
+            let outcome = self.execute_block(finally, environment)?;
+            if outcome != ControlFlow::Normal {
+                return Ok(outcome);
+            }
+        }
+
         result
     }
 }
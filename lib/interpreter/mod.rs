@@ -1,6 +1,10 @@
+#[allow(clippy::module_inception)]
 pub mod interpreter;
 pub mod object;
 pub mod stmt;
 pub mod expr;
 pub mod enviroment;
-pub mod runtime_error;
\ No newline at end of file
+pub mod runtime_error;
+pub mod builtins;
+pub mod resolver;
+pub mod typechecker;
\ No newline at end of file
@@ -1,11 +1,128 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::interpreter::enviroment::Environment;
+use crate::interpreter::runtime_error::InterpreterError;
+use crate::number::format_float;
+use crate::parser::FunctionDecl;
+
 #[derive(Debug, Clone)]
 pub enum Object {
+    // A float; integer literals and integer arithmetic produce `Integer` instead
     Number(f64),
+    Integer(i64),
     Bool(bool),
     String(String),
+    List(Rc<RefCell<Vec<Object>>>),
+    Map(MapEntries),
+    Set(SetEntries),
+    NativeFunction(NativeFunction),
+    Function(Rc<Function>),
+    Module(Rc<Module>),
+    Enum(Rc<Enum>),
+    Variant(Rc<Variant>),
     Nil,
 }
 
+/// Shared, insertion-ordered storage behind a map value.
+pub type MapEntries = Rc<RefCell<IndexMap<HashKey, Object>>>;
+
+/// Shared, insertion-ordered storage behind a set value.
+pub type SetEntries = Rc<RefCell<IndexSet<HashKey>>>;
+
+/// The hashable subset of `Object`, usable as a map key or set element.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HashKey {
+    // Float keys by bit pattern; integral floats are stored as `Integer` so `1.0` finds `1`
+    Number(u64),
+    Integer(i64),
+    Bool(bool),
+    String(String),
+    Nil,
+}
+
+impl HashKey {
+    pub fn to_object(&self) -> Object {
+        match self {
+            HashKey::Number(bits) => Object::Number(f64::from_bits(*bits)),
+            HashKey::Integer(n) => Object::Integer(*n),
+            HashKey::Bool(b) => Object::Bool(*b),
+            HashKey::String(s) => Object::String(s.clone()),
+            HashKey::Nil => Object::Nil,
+        }
+    }
+}
+
+/// A function defined in Lox, with the scope it was created in.
+pub struct Function {
+    pub declaration: Rc<FunctionDecl>,
+    pub closure: Rc<RefCell<Environment>>,
+}
+
+impl fmt::Debug for Function {
+    // The closure usually contains the function itself, so only show the signature
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.declaration.name {
+            Some(name) => write!(f, "<fn {}>", name.lexeme),
+            None => write!(f, "<anonymous fn>"),
+        }
+    }
+}
+
+/// The namespace created by `import`, holding the imported file's top-level bindings.
+#[derive(Debug)]
+pub struct Module {
+    pub name: String,
+    pub values: HashMap<String, Object>,
+}
+
+/// The value bound by an `enum` declaration; its variants are reached as properties.
+#[derive(Debug)]
+pub struct Enum {
+    pub name: String,
+    pub variants: Vec<Rc<Variant>>,
+}
+
+impl Enum {
+    /// Create an enum whose variants are declared in the given order.
+    pub fn new(name: String, variant_names: Vec<String>) -> Self {
+        let variants = variant_names
+            .into_iter()
+            .map(|variant| Rc::new(Variant { enum_name: name.clone(), name: variant }))
+            .collect();
+        Enum { name, variants }
+    }
+
+    pub fn variant(&self, name: &str) -> Option<&Rc<Variant>> {
+        self.variants.iter().find(|variant| variant.name == name)
+    }
+}
+
+/// One variant of an enum. Each is allocated once, so identity is equality.
+#[derive(Debug)]
+pub struct Variant {
+    pub enum_name: String,
+    pub name: String,
+}
+
+/// A function implemented in Rust and exposed to Lox as a global.
+#[derive(Debug, Clone)]
+pub struct NativeFunction {
+    pub name: &'static str,
+    pub arity: usize,
+    pub function: fn(&[Object], usize) -> Result<Object, InterpreterError>,
+}
+
 impl Object {
     pub fn is_truthy(&self) -> bool {
         match self {
@@ -18,17 +135,148 @@ impl Object {
     pub fn as_number(&self) -> Result<f64, String> {
         match self {
             Object::Number(n) => Ok(*n),
+            Object::Integer(n) => Ok(*n as f64),
             _ => Err("Operand must be a number.".to_string()),
         }
     }
+
+    pub fn list(elements: Vec<Object>) -> Object {
+        Object::List(Rc::new(RefCell::new(elements)))
+    }
+
+    pub fn map(entries: IndexMap<HashKey, Object>) -> Object {
+        Object::Map(Rc::new(RefCell::new(entries)))
+    }
+
+    pub fn set(elements: IndexSet<HashKey>) -> Object {
+        Object::Set(Rc::new(RefCell::new(elements)))
+    }
+
+    /// The values a `for-in` loop visits: list elements, map keys, set elements, the
+    /// characters of a string or an enum's variants. Collections are copied so the loop
+    /// body may modify them. `None` if the value can't be iterated.
+    pub fn items(&self) -> Option<Vec<Object>> {
+        match self {
+            Object::List(elements) => Some(elements.borrow().clone()),
+            Object::Map(entries) => Some(entries.borrow().keys().map(HashKey::to_object).collect()),
+            Object::Set(elements) => Some(elements.borrow().iter().map(HashKey::to_object).collect()),
+            Object::String(s) => Some(s.chars().map(|c| Object::String(c.to_string())).collect()),
+            Object::Enum(enumeration) => Some(enumeration.variants.iter().cloned().map(Object::Variant).collect()),
+            _ => None,
+        }
+    }
+
+    /// The map key for this value, or `None` if it can't be hashed.
+    pub fn to_key(&self) -> Option<HashKey> {
+        match self {
+            // Integral floats (including -0.0) share the integer's entry, matching `==`
+            Object::Number(n) => Some(match float_to_integer(*n) {
+                Some(integer) => HashKey::Integer(integer),
+                None => HashKey::Number(n.to_bits()),
+            }),
+            Object::Integer(n) => Some(HashKey::Integer(*n)),
+            Object::Bool(b) => Some(HashKey::Bool(*b)),
+            Object::String(s) => Some(HashKey::String(s.clone())),
+            Object::Nil => Some(HashKey::Nil),
+            _ => None,
+        }
+    }
+
+    /// The value as it appears inside a collection, with strings quoted so `"1"` and `1` differ.
+    pub fn to_repr(&self) -> String {
+        match self {
+            Object::String(s) => format!("{:?}", s),
+            _ => self.to_string(),
+        }
+    }
+
+    // Format a value nested inside a collection, quoting strings like `to_repr`
+    fn fmt_nested(&self, f: &mut fmt::Formatter, enclosing: &mut Vec<*const ()>) -> fmt::Result {
+        match self {
+            Object::String(s) => write!(f, "{:?}", s),
+            _ => self.fmt_tracked(f, enclosing),
+        }
+    }
+
+    // Format a value, given the lists and maps it is nested in. Index assignment can put a
+    // collection inside itself, so one already being printed is shown as `[...]` or `{...}`.
+    fn fmt_tracked(&self, f: &mut fmt::Formatter, enclosing: &mut Vec<*const ()>) -> fmt::Result {
+        match self {
+            Object::List(elements) => {
+                let id = Rc::as_ptr(elements) as *const ();
+                if enclosing.contains(&id) {
+                    return write!(f, "[...]");
+                }
+                enclosing.push(id);
+                write!(f, "[")?;
+                for (i, element) in elements.borrow().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    element.fmt_nested(f, enclosing)?;
+                }
+                enclosing.pop();
+                write!(f, "]")
+            }
+            Object::Map(entries) => {
+                let id = Rc::as_ptr(entries) as *const ();
+                if enclosing.contains(&id) {
+                    return write!(f, "{{...}}");
+                }
+                enclosing.push(id);
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.borrow().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    key.to_object().fmt_nested(f, enclosing)?;
+                    write!(f, ": ")?;
+                    value.fmt_nested(f, enclosing)?;
+                }
+                enclosing.pop();
+                write!(f, "}}")
+            }
+            _ => write!(f, "{}", self),
+        }
+    }
+}
+
+/// The integer a float is exactly equal to, if any.
+pub fn float_to_integer(value: f64) -> Option<i64> {
+    // i64::MAX as f64 rounds up to 2^63, which is itself out of range
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+    if value.fract() == 0.0 && (-LIMIT..LIMIT).contains(&value) {
+        Some(value as i64)
+    } else {
+        None
+    }
 }
 
-impl std::fmt::Display for Object {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl fmt::Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Object::Number(n) => write!(f, "{}", n),
+            Object::Number(n) => write!(f, "{}", format_float(*n)),
+            Object::Integer(n) => write!(f, "{}", n),
             Object::Bool(b) => write!(f, "{}", b),
             Object::String(s) => write!(f, "{}", s),
+            Object::List(_) | Object::Map(_) => self.fmt_tracked(f, &mut Vec::new()),
+            // `{1, 2}` like a map without values; the empty set prints as its constructor call
+            Object::Set(elements) if elements.borrow().is_empty() => write!(f, "set([])"),
+            Object::Set(elements) => {
+                write!(f, "{{")?;
+                for (i, element) in elements.borrow().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    element.to_object().fmt_nested(f, &mut Vec::new())?;
+                }
+                write!(f, "}}")
+            }
+            Object::NativeFunction(native) => write!(f, "<native fn {}>", native.name),
+            Object::Function(function) => write!(f, "{}", function),
+            Object::Module(module) => write!(f, "<module {}>", module.name),
+            Object::Enum(enumeration) => write!(f, "<enum {}>", enumeration.name),
+            Object::Variant(variant) => write!(f, "{}.{}", variant.enum_name, variant.name),
             Object::Nil => write!(f, "nil"),
         }
     }
@@ -38,8 +286,24 @@ impl PartialEq for Object {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Object::Number(a), Object::Number(b)) => a == b,
+            (Object::Integer(a), Object::Integer(b)) => a == b,
+            // Mixed kinds compare by value, exactly: `1 == 1.0` but not `2^53 + 1 == 2^53`
+            (Object::Integer(a), Object::Number(b)) | (Object::Number(b), Object::Integer(a)) => {
+                float_to_integer(*b) == Some(*a)
+            }
             (Object::Bool(a), Object::Bool(b)) => a == b,
             (Object::String(a), Object::String(b)) => a == b,
+            // Lists and maps are shared by reference, so they compare by identity
+            (Object::List(a), Object::List(b)) => Rc::ptr_eq(a, b),
+            (Object::Map(a), Object::Map(b)) => Rc::ptr_eq(a, b),
+            // Set algebra builds new sets, so sets compare by contents, ignoring order
+            (Object::Set(a), Object::Set(b)) => *a.borrow() == *b.borrow(),
+            (Object::NativeFunction(a), Object::NativeFunction(b)) => a.name == b.name,
+            (Object::Function(a), Object::Function(b)) => Rc::ptr_eq(a, b),
+            (Object::Module(a), Object::Module(b)) => Rc::ptr_eq(a, b),
+            // Variants of two separately declared enums never compare equal, even with the same names
+            (Object::Enum(a), Object::Enum(b)) => Rc::ptr_eq(a, b),
+            (Object::Variant(a), Object::Variant(b)) => Rc::ptr_eq(a, b),
             (Object::Nil, Object::Nil) => true,
             _ => false,
         }
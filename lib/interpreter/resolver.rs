@@ -0,0 +1,383 @@
+use crate::interpreter::expr::ExprVisitor;
+use crate::interpreter::stmt::StmtVisitor;
+use crate::parser::{Binding, CatchClause, Expr, FunctionDecl, Literal, MatchArm, Pattern, Stmt};
+use crate::token::token::Token;
+use std::collections::HashMap;
+use std::rc::Rc;
+use thiserror::Error;
+
+/// A mistake found by the resolver before the program runs.
+#[derive(Debug, Error)]
+#[error("Error on line {line}: {message}")]
+pub struct ResolveError {
+    pub message: String,
+    pub line: usize,
+}
+
+/// Something suspicious but legal, reported without stopping the program.
+#[derive(Debug, Error)]
+#[error("Warning on line {line}: {message}")]
+pub struct ResolveWarning {
+    pub message: String,
+    pub line: usize,
+}
+
+/// What a name was declared as, as far as the resolver cares.
+enum Declaration {
+    Variable,
+    Constant,
+    // An enum and the names of its variants
+    Enum(Vec<String>),
+}
+
+/// A static pass over the parsed program that tracks which names each scope
+/// declares, so misuse of bindings is reported before anything executes.
+pub struct Resolver {
+    // Innermost scope last
+    scopes: Vec<HashMap<String, Declaration>>,
+    errors: Vec<ResolveError>,
+    warnings: Vec<ResolveWarning>,
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Resolver {
+            scopes: vec![HashMap::new()],
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Check a whole program, returning every error found.
+    pub fn resolve(&mut self, statements: &[Stmt]) -> Result<(), Vec<ResolveError>> {
+        self.resolve_statements(statements);
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(std::mem::take(&mut self.errors))
+        }
+    }
+
+    /// Warnings collected so far, leaving none behind.
+    pub fn take_warnings(&mut self) -> Vec<ResolveWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    fn resolve_statements(&mut self, statements: &[Stmt]) {
+        for statement in statements {
+            statement.accept(self);
+        }
+    }
+
+    fn resolve_expr(&mut self, expr: &Expr) {
+        expr.accept(self);
+    }
+
+    // Resolve statements in a fresh scope, optionally binding one name first
+    fn resolve_scoped(&mut self, statements: &[Stmt], binding: Option<&Token>) {
+        self.scopes.push(HashMap::new());
+        if let Some(name) = binding {
+            self.declare(name, Declaration::Variable);
+        }
+        self.resolve_statements(statements);
+        self.scopes.pop();
+    }
+
+    fn resolve_function(&mut self, function: &FunctionDecl) {
+        self.scopes.push(function.params.iter().map(|param| (param.lexeme.clone(), Declaration::Variable)).collect());
+        self.resolve_statements(&function.body);
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, name: &Token, declaration: Declaration) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.lexeme.clone(), declaration);
+        }
+    }
+
+    // The nearest visible declaration of a name, if it has been seen yet
+    fn lookup(&self, name: &str) -> Option<&Declaration> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    // Report an assignment to a name whose nearest visible declaration is `const` or an enum.
+    // Names not declared yet (e.g. defined later at top level) are left to the runtime check.
+    fn check_assignable(&mut self, name: &Token) {
+        let kind = match self.lookup(&name.lexeme) {
+            Some(Declaration::Constant) => "constant",
+            Some(Declaration::Enum(_)) => "enum",
+            _ => return,
+        };
+        self.errors.push(ResolveError {
+            message: format!("Cannot assign to {} '{}'.", kind, name.lexeme),
+            line: name.line,
+        });
+    }
+
+    // Report `Enum.Variant` when the enum is known and has no such variant
+    fn check_variant(&mut self, enum_name: &Token, variant: &Token) {
+        let Some(Declaration::Enum(variants)) = self.lookup(&enum_name.lexeme) else {
+            return;
+        };
+        if !variants.contains(&variant.lexeme) {
+            self.errors.push(ResolveError {
+                message: format!("Enum '{}' has no variant '{}'.", enum_name.lexeme, variant.lexeme),
+                line: variant.line,
+            });
+        }
+    }
+
+    fn check_target(&mut self, target: &Expr) {
+        if let Expr::Variable(name) = target {
+            self.check_assignable(name);
+        }
+    }
+}
+
+impl ExprVisitor<()> for Resolver {
+    fn visit_literal_expr(&mut self, _expr: &Literal) {}
+
+    fn visit_binary_expr(&mut self, left: &Expr, _operator: &Token, right: &Expr) {
+        self.resolve_expr(left);
+        self.resolve_expr(right);
+    }
+
+    fn visit_grouping_expr(&mut self, expr: &Expr) {
+        self.resolve_expr(expr);
+    }
+
+    fn visit_unary_expr(&mut self, _operator: &Token, right: &Expr) {
+        self.resolve_expr(right);
+    }
+
+    fn visit_variable_expr(&mut self, _token: &Token) {}
+
+    fn visit_assign_expr(&mut self, name: &Token, value: &Expr) {
+        self.resolve_expr(value);
+        self.check_assignable(name);
+    }
+
+    fn visit_interpolation_expr(&mut self, parts: &[Expr]) {
+        for part in parts {
+            self.resolve_expr(part);
+        }
+    }
+
+    fn visit_list_expr(&mut self, elements: &[Expr]) {
+        for element in elements {
+            self.resolve_expr(element);
+        }
+    }
+
+    fn visit_map_expr(&mut self, _brace: &Token, entries: &[(Expr, Expr)]) {
+        for (key, value) in entries {
+            self.resolve_expr(key);
+            self.resolve_expr(value);
+        }
+    }
+
+    fn visit_index_expr(&mut self, object: &Expr, _bracket: &Token, index: &Expr) {
+        self.resolve_expr(object);
+        self.resolve_expr(index);
+    }
+
+    fn visit_index_assign_expr(&mut self, object: &Expr, _bracket: &Token, index: &Expr, value: &Expr) {
+        self.resolve_expr(object);
+        self.resolve_expr(index);
+        self.resolve_expr(value);
+    }
+
+    fn visit_call_expr(&mut self, callee: &Expr, _paren: &Token, arguments: &[Expr]) {
+        self.resolve_expr(callee);
+        for argument in arguments {
+            self.resolve_expr(argument);
+        }
+    }
+
+    fn visit_get_expr(&mut self, object: &Expr, name: &Token) {
+        self.resolve_expr(object);
+        if let Expr::Variable(enum_name) = object {
+            self.check_variant(enum_name, name);
+        }
+    }
+
+    fn visit_conditional_expr(&mut self, condition: &Expr, then_branch: &Expr, else_branch: &Expr) {
+        self.resolve_expr(condition);
+        self.resolve_expr(then_branch);
+        self.resolve_expr(else_branch);
+    }
+
+    fn visit_comma_expr(&mut self, left: &Expr, right: &Expr) {
+        self.resolve_expr(left);
+        self.resolve_expr(right);
+    }
+
+    fn visit_compound_assign_expr(&mut self, target: &Expr, _operator: &Token, value: &Expr) {
+        self.resolve_expr(target);
+        self.resolve_expr(value);
+        self.check_target(target);
+    }
+
+    fn visit_prefix_update_expr(&mut self, _operator: &Token, target: &Expr) {
+        self.resolve_expr(target);
+        self.check_target(target);
+    }
+
+    fn visit_postfix_update_expr(&mut self, target: &Expr, _operator: &Token) {
+        self.resolve_expr(target);
+        self.check_target(target);
+    }
+
+    fn visit_lambda_expr(&mut self, function: &Rc<FunctionDecl>) {
+        self.resolve_function(function);
+    }
+
+    fn visit_nil_coalesce_expr(&mut self, left: &Expr, _operator: &Token, right: &Expr) {
+        self.resolve_expr(left);
+        self.resolve_expr(right);
+    }
+
+    fn visit_optional_get_expr(&mut self, object: &Expr, name: &Token) {
+        self.resolve_expr(object);
+        if let Expr::Variable(enum_name) = object {
+            self.check_variant(enum_name, name);
+        }
+    }
+
+    fn visit_optional_chain_expr(&mut self, chain: &Expr) {
+        self.resolve_expr(chain);
+    }
+
+    fn visit_list_assign_expr(&mut self, names: &[Token], _equals: &Token, value: &Expr) {
+        self.resolve_expr(value);
+        for name in names {
+            self.check_assignable(name);
+        }
+    }
+}
+
+impl StmtVisitor<()> for Resolver {
+    fn visit_print_stmt(&mut self, expr: &Expr) {
+        self.resolve_expr(expr);
+    }
+
+    fn visit_var_stmt(&mut self, binding: &Binding, _annotation: Option<&Token>, initializer: Option<&Expr>) {
+        if let Some(initializer) = initializer {
+            self.resolve_expr(initializer);
+        }
+        for name in binding.names() {
+            self.declare(name, Declaration::Variable);
+        }
+    }
+
+    fn visit_const_stmt(&mut self, name: &Token, _annotation: Option<&Token>, initializer: &Expr) {
+        self.resolve_expr(initializer);
+        self.declare(name, Declaration::Constant);
+    }
+
+    fn visit_expression_stmt(&mut self, expr: &Expr) {
+        self.resolve_expr(expr);
+    }
+
+    fn visit_block_stmt(&mut self, statements: &[Stmt]) {
+        self.resolve_scoped(statements, None);
+    }
+
+    fn visit_if_stmt(&mut self, condition: &Expr, then_branch: &Stmt, else_branch: Option<&Stmt>) {
+        self.resolve_expr(condition);
+        then_branch.accept(self);
+        if let Some(else_branch) = else_branch {
+            else_branch.accept(self);
+        }
+    }
+
+    fn visit_while_stmt(&mut self, condition: &Expr, body: &Stmt, increment: Option<&Expr>) {
+        self.resolve_expr(condition);
+        body.accept(self);
+        if let Some(increment) = increment {
+            self.resolve_expr(increment);
+        }
+    }
+
+    fn visit_for_in_stmt(&mut self, name: &Token, _keyword: &Token, iterable: &Expr, end: Option<&Expr>, body: &Stmt) {
+        self.resolve_expr(iterable);
+        if let Some(end) = end {
+            self.resolve_expr(end);
+        }
+        self.resolve_scoped(std::slice::from_ref(body), Some(name));
+    }
+
+    fn visit_break_stmt(&mut self, _keyword: &Token) {}
+
+    fn visit_continue_stmt(&mut self, _keyword: &Token) {}
+
+    fn visit_throw_stmt(&mut self, _keyword: &Token, value: &Expr) {
+        self.resolve_expr(value);
+    }
+
+    fn visit_assert_stmt(&mut self, _keyword: &Token, condition: &Expr, _source: &str, message: Option<&Expr>) {
+        self.resolve_expr(condition);
+        if let Some(message) = message {
+            self.resolve_expr(message);
+        }
+    }
+
+    fn visit_import_stmt(&mut self, _keyword: &Token, _path: &Token, alias: &Token) {
+        self.declare(alias, Declaration::Variable);
+    }
+
+    fn visit_function_stmt(&mut self, function: &Rc<FunctionDecl>) {
+        // Declared before the body so the function can call itself
+        if let Some(name) = &function.name {
+            self.declare(name, Declaration::Variable);
+        }
+        self.resolve_function(function);
+    }
+
+    fn visit_return_stmt(&mut self, _keyword: &Token, value: Option<&Expr>) {
+        if let Some(value) = value {
+            self.resolve_expr(value);
+        }
+    }
+
+    fn visit_match_stmt(&mut self, _keyword: &Token, subject: &Expr, arms: &[MatchArm]) {
+        self.resolve_expr(subject);
+        let mut after_wildcard = false;
+        for arm in arms {
+            if after_wildcard {
+                self.warnings.push(ResolveWarning {
+                    message: "Unreachable match arm after a '_' pattern.".to_string(),
+                    line: arm.arrow.line,
+                });
+            }
+            for pattern in &arm.patterns {
+                if let Pattern::Variant { enum_name, variant } = pattern {
+                    self.check_variant(enum_name, variant);
+                }
+            }
+            after_wildcard |= arm.patterns.iter().any(|pattern| matches!(pattern, Pattern::Wildcard));
+            arm.body.accept(self);
+        }
+    }
+
+    fn visit_enum_stmt(&mut self, name: &Token, variants: &[Token]) {
+        let variant_names = variants.iter().map(|variant| variant.lexeme.clone()).collect();
+        self.declare(name, Declaration::Enum(variant_names));
+    }
+
+    fn visit_try_stmt(&mut self, body: &[Stmt], catch: Option<&CatchClause>, finally: Option<&[Stmt]>) {
+        self.resolve_scoped(body, None);
+        if let Some(catch) = catch {
+            self.resolve_scoped(&catch.body, Some(&catch.name));
+        }
+        if let Some(finally) = finally {
+            self.resolve_scoped(finally, None);
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use crate::interpreter::object::{HashKey, Object};
 use crate::parser::ParseError;
+use indexmap::IndexMap;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -9,12 +11,107 @@ pub enum InterpreterError {
     #[error("Operands must be two numbers or two strings. [line {line}]")]
     InvalidBinaryOperands { line: usize },
 
+    #[error("Operands must be numbers. [line {line}]")]
+    InvalidNumericOperands { line: usize },
+
+    #[error("Operands of '{operator}' must be integers within ±2^53. [line {line}]")]
+    NonIntegerOperand { operator: String, line: usize },
+
+    #[error("Integer overflow in '{operator}'. [line {line}]")]
+    IntegerOverflow { operator: String, line: usize },
+
+    #[error("Shift amount must be between 0 and 63. [line {line}]")]
+    InvalidShift { line: usize },
+
     #[error("Undefined variable '{name}' at line {line}.")]
     UndefinedVariable { name: String, line: usize },
 
+    #[error("Cannot assign to constant '{name}' at line {line}.")]
+    ConstantAssignment { name: String, line: usize },
+
     #[error("Division by zero at line {line}.")]
     DivisionByZero { line: usize },
 
+    #[error("Modulo by zero at line {line}.")]
+    ModuloByZero { line: usize },
+
+    #[error("Index must be an integer. [line {line}]")]
+    InvalidIndex { line: usize },
+
+    #[error("Negative index {index} is not allowed. [line {line}]")]
+    NegativeIndex { index: i64, line: usize },
+
+    #[error("Index {index} out of bounds for length {len}. [line {line}]")]
+    IndexOutOfBounds { index: usize, len: usize, line: usize },
+
+    #[error("Map keys and set elements must be numbers, strings, booleans or nil. [line {line}]")]
+    UnhashableKey { line: usize },
+
+    #[error("Undefined key {key}. [line {line}]")]
+    UndefinedKey { key: String, line: usize },
+
+    #[error("Only lists and maps can be indexed. [line {line}]")]
+    NotIndexable { line: usize },
+
+    #[error("Only lists, maps, sets, strings, enums and ranges can be iterated. [line {line}]")]
+    NotIterable { line: usize },
+
+    #[error("Range bounds must be numbers. [line {line}]")]
+    InvalidRange { line: usize },
+
+    #[error("Float ranges must have finite bounds at most {limit} apart. [line {line}]")]
+    FloatRangeTooLong { limit: u64, line: usize },
+
+    #[error("Can only call functions. [line {line}]")]
+    NotCallable { line: usize },
+
+    #[error("Expected {expected} arguments but got {got}. [line {line}]")]
+    ArityMismatch { expected: usize, got: usize, line: usize },
+
+    #[error("{message} [line {line}]")]
+    InvalidArgument { message: String, line: usize },
+
+    #[error("Cannot destructure '{name}': the list has no element at index {index}. [line {line}]")]
+    MissingElement { name: String, index: usize, line: usize },
+
+    #[error("Cannot destructure '{name}': no such key or binding. [line {line}]")]
+    MissingField { name: String, line: usize },
+
+    #[error("Only {expected} can be destructured with this pattern. [line {line}]")]
+    NotDestructurable { expected: String, line: usize },
+
+    #[error("Only maps, modules and enums have properties. [line {line}]")]
+    NoProperties { line: usize },
+
+    #[error("Undefined property '{name}'. [line {line}]")]
+    UndefinedProperty { name: String, line: usize },
+
+    #[error("Enum '{enum_name}' has no variant '{name}'. [line {line}]")]
+    UndefinedVariant { enum_name: String, name: String, line: usize },
+
+    #[error("Cannot import '{path}': {message} [line {line}]")]
+    ImportFailed { path: String, message: String, line: usize },
+
+    // A tokenizer, parse or resolver error inside an imported file; `line` is a line of that file
+    #[error("Error in imported file '{path}' on line {line}: {message}")]
+    InvalidModule { path: String, message: String, line: usize },
+
+    #[error("Import cycle detected: {cycle} [line {line}]")]
+    ImportCycle { cycle: String, line: usize },
+
+    #[error("{path}: {source}")]
+    InModule { path: String, source: Box<InterpreterError> },
+
+    #[error("{source}\n    in {function} defined at line {line}")]
+    InFunction { function: String, line: usize, source: Box<InterpreterError> },
+
+    // `operands` shows the values of a failed comparison, e.g. `3 != 4` for `assert x == y`
+    #[error("assert {condition} failed{} [line {line}]", assertion_details(.operands, .message))]
+    AssertionFailed { condition: String, operands: Option<String>, message: Option<String>, line: usize },
+
+    #[error("Uncaught exception: {value} [line {line}]")]
+    Thrown { value: Object, line: usize },
+
     #[error("Runtime Error: {message}")]
     GenericError { message: String },
 
@@ -24,3 +121,91 @@ pub enum InterpreterError {
         source: ParseError,
     },
 }
+
+impl InterpreterError {
+    /// The source line the error was raised on, if it is known.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            InterpreterError::InvalidUnaryOperand { line }
+            | InterpreterError::InvalidBinaryOperands { line }
+            | InterpreterError::InvalidNumericOperands { line }
+            | InterpreterError::NonIntegerOperand { line, .. }
+            | InterpreterError::InvalidShift { line }
+            | InterpreterError::UndefinedVariable { line, .. }
+            | InterpreterError::DivisionByZero { line }
+            | InterpreterError::ModuloByZero { line }
+            | InterpreterError::InvalidIndex { line }
+            | InterpreterError::NegativeIndex { line, .. }
+            | InterpreterError::IndexOutOfBounds { line, .. }
+            | InterpreterError::UnhashableKey { line }
+            | InterpreterError::UndefinedKey { line, .. }
+            | InterpreterError::NotIndexable { line }
+            | InterpreterError::NotCallable { line }
+            | InterpreterError::NotIterable { line }
+            | InterpreterError::InvalidRange { line }
+            | InterpreterError::FloatRangeTooLong { line, .. }
+            | InterpreterError::ArityMismatch { line, .. }
+            | InterpreterError::InvalidArgument { line, .. }
+            | InterpreterError::NoProperties { line }
+            | InterpreterError::UndefinedProperty { line, .. }
+            | InterpreterError::UndefinedVariant { line, .. }
+            | InterpreterError::ImportFailed { line, .. }
+            | InterpreterError::ImportCycle { line, .. }
+            | InterpreterError::InvalidModule { line, .. }
+            | InterpreterError::ConstantAssignment { line, .. }
+            | InterpreterError::IntegerOverflow { line, .. }
+            | InterpreterError::MissingElement { line, .. }
+            | InterpreterError::MissingField { line, .. }
+            | InterpreterError::NotDestructurable { line, .. }
+            | InterpreterError::AssertionFailed { line, .. }
+            | InterpreterError::Thrown { line, .. } => Some(*line),
+            InterpreterError::InModule { source, .. }
+            | InterpreterError::InFunction { source, .. } => source.line(),
+            InterpreterError::GenericError { .. } | InterpreterError::ParseError { .. } => None,
+        }
+    }
+
+    /// Whether a `catch` clause may handle this error. Thrown values and built-in runtime
+    /// errors can be caught; failed asserts, import failures and internal errors propagate.
+    pub fn is_catchable(&self) -> bool {
+        match self {
+            InterpreterError::InModule { source, .. }
+            | InterpreterError::InFunction { source, .. } => source.is_catchable(),
+            InterpreterError::AssertionFailed { .. }
+            | InterpreterError::ImportFailed { .. }
+            | InterpreterError::ImportCycle { .. }
+            | InterpreterError::InvalidModule { .. }
+            | InterpreterError::GenericError { .. }
+            | InterpreterError::ParseError { .. } => false,
+            _ => true,
+        }
+    }
+
+    /// The value a `catch` clause binds for this error: the thrown value itself, or for
+    /// built-in errors a map with `message` and `line` entries.
+    pub fn to_value(&self) -> Object {
+        match self {
+            InterpreterError::Thrown { value, .. } => return value.clone(),
+            InterpreterError::InModule { source, .. }
+            | InterpreterError::InFunction { source, .. } => return source.to_value(),
+            _ => {}
+        }
+
+        let mut entries = IndexMap::new();
+        entries.insert(HashKey::String("message".to_string()), Object::String(self.to_string()));
+        entries.insert(
+            HashKey::String("line".to_string()),
+            self.line().map_or(Object::Nil, |line| Object::Integer(line as i64)),
+        );
+        Object::map(entries)
+    }
+}
+
+// The part of an assertion failure after "failed": the compared values and the user's message
+fn assertion_details(operands: &Option<String>, message: &Option<String>) -> String {
+    match (operands, message) {
+        (Some(operands), Some(message)) => format!(": {} ({})", operands, message),
+        (Some(details), None) | (None, Some(details)) => format!(": {}", details),
+        (None, None) => String::new(),
+    }
+}
@@ -1,12 +1,27 @@
-use crate::parser::{Expr, Stmt};
+use crate::parser::{Binding, CatchClause, Expr, FunctionDecl, MatchArm, Stmt};
 use crate::token::token::Token;
+use std::rc::Rc;
 
 // Statement Visitor Trait
 pub trait StmtVisitor<T> {
     fn visit_print_stmt(&mut self, expr: &Expr) -> T;
-    fn visit_var_stmt(&mut self, name: &Token, initializer: Option<&Expr>) -> T;
+    fn visit_var_stmt(&mut self, binding: &Binding, annotation: Option<&Token>, initializer: Option<&Expr>) -> T;
+    fn visit_const_stmt(&mut self, name: &Token, annotation: Option<&Token>, initializer: &Expr) -> T;
     fn visit_expression_stmt(&mut self, expr: &Expr) -> T;
     fn visit_block_stmt(&mut self, statements: &[Stmt]) -> T;  
+    fn visit_if_stmt(&mut self, condition: &Expr, then_branch: &Stmt, else_branch: Option<&Stmt>) -> T;
+    fn visit_while_stmt(&mut self, condition: &Expr, body: &Stmt, increment: Option<&Expr>) -> T;
+    fn visit_for_in_stmt(&mut self, name: &Token, keyword: &Token, iterable: &Expr, end: Option<&Expr>, body: &Stmt) -> T;
+    fn visit_break_stmt(&mut self, keyword: &Token) -> T;
+    fn visit_continue_stmt(&mut self, keyword: &Token) -> T;
+    fn visit_throw_stmt(&mut self, keyword: &Token, value: &Expr) -> T;
+    fn visit_assert_stmt(&mut self, keyword: &Token, condition: &Expr, source: &str, message: Option<&Expr>) -> T;
+    fn visit_import_stmt(&mut self, keyword: &Token, path: &Token, alias: &Token) -> T;
+    fn visit_function_stmt(&mut self, function: &Rc<FunctionDecl>) -> T;
+    fn visit_return_stmt(&mut self, keyword: &Token, value: Option<&Expr>) -> T;
+    fn visit_match_stmt(&mut self, keyword: &Token, subject: &Expr, arms: &[MatchArm]) -> T;
+    fn visit_enum_stmt(&mut self, name: &Token, variants: &[Token]) -> T;
+    fn visit_try_stmt(&mut self, body: &[Stmt], catch: Option<&CatchClause>, finally: Option<&[Stmt]>) -> T;
 }
 
 
@@ -15,12 +30,40 @@ impl Stmt {
     pub fn accept<T>(&self, visitor: &mut dyn StmtVisitor<T>) -> T {
         match self {
             Stmt::Print(expr) => visitor.visit_print_stmt(expr),
-            Stmt::Var { name, initializer } => visitor.visit_var_stmt(name, initializer.as_ref()),
+            Stmt::Var { binding, annotation, initializer } => {
+                visitor.visit_var_stmt(binding, annotation.as_ref(), initializer.as_ref())
+            }
+            Stmt::Const { name, annotation, initializer } => {
+                visitor.visit_const_stmt(name, annotation.as_ref(), initializer)
+            }
             Stmt::Expression(expr) => visitor.visit_expression_stmt(expr),
             Stmt::Block(statements) => {
 
                 visitor.visit_block_stmt(statements)
             }
+            Stmt::If { condition, then_branch, else_branch } => {
+                visitor.visit_if_stmt(condition, then_branch, else_branch.as_deref())
+            }
+            Stmt::While { condition, body, increment } => {
+                visitor.visit_while_stmt(condition, body, increment.as_ref())
+            }
+            Stmt::ForIn { name, keyword, iterable, end, body } => {
+                visitor.visit_for_in_stmt(name, keyword, iterable, end.as_ref(), body)
+            }
+            Stmt::Break(keyword) => visitor.visit_break_stmt(keyword),
+            Stmt::Continue(keyword) => visitor.visit_continue_stmt(keyword),
+            Stmt::Import { keyword, path, alias } => visitor.visit_import_stmt(keyword, path, alias),
+            Stmt::Function(function) => visitor.visit_function_stmt(function),
+            Stmt::Return { keyword, value } => visitor.visit_return_stmt(keyword, value.as_ref()),
+            Stmt::Match { keyword, subject, arms } => visitor.visit_match_stmt(keyword, subject, arms),
+            Stmt::Enum { name, variants } => visitor.visit_enum_stmt(name, variants),
+            Stmt::Throw { keyword, value } => visitor.visit_throw_stmt(keyword, value),
+            Stmt::Assert { keyword, condition, source, message } => {
+                visitor.visit_assert_stmt(keyword, condition, source, message.as_ref())
+            }
+            Stmt::Try { body, catch, finally } => {
+                visitor.visit_try_stmt(body, catch.as_ref(), finally.as_deref())
+            }
         }
     }
 }
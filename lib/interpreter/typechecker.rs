@@ -0,0 +1,562 @@
+use crate::interpreter::expr::ExprVisitor;
+use crate::interpreter::stmt::StmtVisitor;
+use crate::parser::{Binding, CatchClause, Expr, FunctionDecl, Literal, MatchArm, Stmt};
+use crate::token::token::{Token, TokenType};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use thiserror::Error;
+
+/// The types the checker can tell apart. Anything it can't pin down, such as an
+/// unannotated variable, is `Any` and is accepted everywhere.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Type {
+    Number,
+    String,
+    Bool,
+    Nil,
+    List,
+    Map,
+    Set,
+    Function,
+    Module,
+    Enum,
+    Any,
+}
+
+impl Type {
+    /// The type named by an annotation such as `var x: number`.
+    pub fn from_name(name: &str) -> Option<Type> {
+        match name {
+            "number" => Some(Type::Number),
+            "string" => Some(Type::String),
+            "bool" => Some(Type::Bool),
+            "nil" => Some(Type::Nil),
+            "list" => Some(Type::List),
+            "map" => Some(Type::Map),
+            "set" => Some(Type::Set),
+            "function" => Some(Type::Function),
+            "module" => Some(Type::Module),
+            "enum" => Some(Type::Enum),
+            "any" => Some(Type::Any),
+            _ => None,
+        }
+    }
+
+    // Whether a value of type `actual` can be used where `self` is expected
+    fn accepts(self, actual: Type) -> bool {
+        self == Type::Any || actual == Type::Any || self == actual
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Type::Number => "number",
+            Type::String => "string",
+            Type::Bool => "bool",
+            Type::Nil => "nil",
+            Type::List => "list",
+            Type::Map => "map",
+            Type::Set => "set",
+            Type::Function => "function",
+            Type::Module => "module",
+            Type::Enum => "enum",
+            Type::Any => "any",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A type mismatch found by the `typecheck` command.
+#[derive(Debug, Error)]
+#[error("Type error on line {line}: {message}")]
+pub struct TypeError {
+    pub message: String,
+    pub line: usize,
+}
+
+/// A gradual type checker: it infers types for literals and operators, checks
+/// them against annotations, and treats everything unannotated as `Any`.
+pub struct TypeChecker {
+    // Innermost scope last
+    scopes: Vec<HashMap<String, Type>>,
+    errors: Vec<TypeError>,
+}
+
+impl Default for TypeChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeChecker {
+    pub fn new() -> Self {
+        TypeChecker {
+            scopes: vec![HashMap::new()],
+            errors: Vec::new(),
+        }
+    }
+
+    /// Check a whole program, returning every mismatch found.
+    pub fn check(&mut self, statements: &[Stmt]) -> Result<(), Vec<TypeError>> {
+        self.check_statements(statements);
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(std::mem::take(&mut self.errors))
+        }
+    }
+
+    fn check_statements(&mut self, statements: &[Stmt]) {
+        for statement in statements {
+            statement.accept(self);
+        }
+    }
+
+    fn check_scoped(&mut self, statements: &[Stmt], bindings: HashMap<String, Type>) {
+        self.scopes.push(bindings);
+        self.check_statements(statements);
+        self.scopes.pop();
+    }
+
+    fn check_function(&mut self, function: &FunctionDecl) {
+        let params = function.params.iter().map(|param| (param.lexeme.clone(), Type::Any)).collect();
+        self.check_scoped(&function.body, params);
+    }
+
+    fn type_of(&mut self, expr: &Expr) -> Type {
+        expr.accept(self)
+    }
+
+    fn error(&mut self, line: usize, message: String) {
+        self.errors.push(TypeError { message, line });
+    }
+
+    fn declare(&mut self, name: &Token, declared: Type) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.lexeme.clone(), declared);
+        }
+    }
+
+    fn lookup(&self, name: &Token) -> Type {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&name.lexeme))
+            .copied()
+            .unwrap_or(Type::Any)
+    }
+
+    // Check an initializer, if any, against an optional annotation and return the type to record
+    fn declared_type(&mut self, name: &Token, annotation: Option<&Token>, actual: Option<Type>) -> Option<Type> {
+        let annotation = annotation?;
+        let Some(declared) = Type::from_name(&annotation.lexeme) else {
+            self.error(annotation.line, format!("Unknown type '{}'.", annotation.lexeme));
+            return Some(Type::Any);
+        };
+        // A variable declared without a value holds nil only until its first assignment
+        let Some(actual) = actual else {
+            return Some(declared);
+        };
+        if !declared.accepts(actual) {
+            self.error(
+                name.line,
+                format!("Cannot initialize '{}' of type {} with {}.", name.lexeme, declared, actual),
+            );
+        }
+        Some(declared)
+    }
+
+    // Require an operand to be a number, as `visit_unary_expr` and `binary_op` do at runtime
+    fn expect_number(&mut self, operand: Type, operator: &Token) {
+        if !Type::Number.accepts(operand) {
+            self.error(
+                operator.line,
+                format!("Operand of '{}' must be a number, found {}.", operator.lexeme, operand),
+            );
+        }
+    }
+
+    /// The result of a binary operator, following the rules of `binary_op`.
+    fn binary_type(&mut self, left: Type, operator: &Token, operator_type: &TokenType, right: Type) -> Type {
+        match operator_type {
+            TokenType::Plus => match (left, right) {
+                (Type::Number, Type::Number) | (Type::String, Type::String) => left,
+                // Only one outcome can succeed once a side is known to be a number or string
+                (Type::Any, Type::Number | Type::String) => right,
+                (Type::Number | Type::String, Type::Any) | (Type::Any, Type::Any) => left,
+                _ => {
+                    self.error(
+                        operator.line,
+                        format!(
+                            "Operands of '{}' must be two numbers or two strings, found {} and {}.",
+                            operator.lexeme, left, right
+                        ),
+                    );
+                    Type::Any
+                }
+            },
+            // `|`, `&` and `-` also combine two sets
+            TokenType::Pipe | TokenType::Ampersand | TokenType::Minus if left == Type::Set || right == Type::Set => {
+                if !Type::Set.accepts(left) || !Type::Set.accepts(right) {
+                    self.error(
+                        operator.line,
+                        format!(
+                            "Operands of '{}' must be two numbers or two sets, found {} and {}.",
+                            operator.lexeme, left, right
+                        ),
+                    );
+                }
+                Type::Set
+            }
+            TokenType::Pipe | TokenType::Ampersand | TokenType::Minus if left == Type::Any && right == Type::Any => {
+                Type::Any
+            }
+            TokenType::Equal_Equal | TokenType::Bang_Equal => Type::Bool,
+            TokenType::Greater | TokenType::Greater_Equal | TokenType::Less | TokenType::Less_Equal => {
+                self.expect_numbers(left, operator, right);
+                Type::Bool
+            }
+            // The arithmetic, bitwise and shift operators all take and produce numbers
+            _ => {
+                self.expect_numbers(left, operator, right);
+                Type::Number
+            }
+        }
+    }
+
+    fn expect_numbers(&mut self, left: Type, operator: &Token, right: Type) {
+        if !Type::Number.accepts(left) || !Type::Number.accepts(right) {
+            self.error(
+                operator.line,
+                format!("Operands of '{}' must be numbers, found {} and {}.", operator.lexeme, left, right),
+            );
+        }
+    }
+
+    // Check a value stored into an assignment target against the target's declared type
+    fn check_store(&mut self, target: &Expr, value: Type, line: usize) {
+        if let Expr::Variable(name) = target {
+            let declared = self.lookup(name);
+            if !declared.accepts(value) {
+                self.error(
+                    line,
+                    format!("Cannot assign {} to '{}' of type {}.", value, name.lexeme, declared),
+                );
+            }
+        }
+    }
+}
+
+impl ExprVisitor<Type> for TypeChecker {
+    fn visit_literal_expr(&mut self, expr: &Literal) -> Type {
+        match expr {
+            Literal::Number(_) | Literal::Integer(_) => Type::Number,
+            Literal::String(_) => Type::String,
+            Literal::Bool(_) => Type::Bool,
+            Literal::Nil => Type::Nil,
+        }
+    }
+
+    fn visit_binary_expr(&mut self, left: &Expr, operator: &Token, right: &Expr) -> Type {
+        let left = self.type_of(left);
+        let right = self.type_of(right);
+        match operator.token_type {
+            // `and`/`or` produce one of their operands
+            TokenType::And | TokenType::Or if left == right => left,
+            TokenType::And | TokenType::Or => Type::Any,
+            _ => self.binary_type(left, operator, &operator.token_type, right),
+        }
+    }
+
+    fn visit_grouping_expr(&mut self, expr: &Expr) -> Type {
+        self.type_of(expr)
+    }
+
+    fn visit_unary_expr(&mut self, operator: &Token, right: &Expr) -> Type {
+        let operand = self.type_of(right);
+        match operator.token_type {
+            TokenType::Bang => Type::Bool,
+            _ => {
+                self.expect_number(operand, operator);
+                Type::Number
+            }
+        }
+    }
+
+    fn visit_variable_expr(&mut self, token: &Token) -> Type {
+        self.lookup(token)
+    }
+
+    fn visit_assign_expr(&mut self, name: &Token, value: &Expr) -> Type {
+        let value = self.type_of(value);
+        self.check_store(&Expr::Variable(name.clone()), value, name.line);
+        value
+    }
+
+    fn visit_interpolation_expr(&mut self, parts: &[Expr]) -> Type {
+        for part in parts {
+            self.type_of(part);
+        }
+        Type::String
+    }
+
+    fn visit_list_expr(&mut self, elements: &[Expr]) -> Type {
+        for element in elements {
+            self.type_of(element);
+        }
+        Type::List
+    }
+
+    fn visit_map_expr(&mut self, _brace: &Token, entries: &[(Expr, Expr)]) -> Type {
+        for (key, value) in entries {
+            self.type_of(key);
+            self.type_of(value);
+        }
+        Type::Map
+    }
+
+    fn visit_index_expr(&mut self, object: &Expr, bracket: &Token, index: &Expr) -> Type {
+        let object = self.type_of(object);
+        self.type_of(index);
+        if !matches!(object, Type::List | Type::Map | Type::Any) {
+            self.error(bracket.line, format!("Only lists and maps can be indexed, found {}.", object));
+        }
+        Type::Any
+    }
+
+    fn visit_index_assign_expr(&mut self, object: &Expr, bracket: &Token, index: &Expr, value: &Expr) -> Type {
+        self.visit_index_expr(object, bracket, index);
+        self.type_of(value)
+    }
+
+    fn visit_call_expr(&mut self, callee: &Expr, paren: &Token, arguments: &[Expr]) -> Type {
+        let callee = self.type_of(callee);
+        for argument in arguments {
+            self.type_of(argument);
+        }
+        if !Type::Function.accepts(callee) {
+            self.error(paren.line, format!("Can only call functions, found {}.", callee));
+        }
+        Type::Any
+    }
+
+    fn visit_get_expr(&mut self, object: &Expr, name: &Token) -> Type {
+        let object = self.type_of(object);
+        if !matches!(object, Type::Map | Type::Module | Type::Enum | Type::Any) {
+            self.error(name.line, format!("Only maps, modules and enums have properties, found {}.", object));
+        }
+        Type::Any
+    }
+
+    fn visit_conditional_expr(&mut self, condition: &Expr, then_branch: &Expr, else_branch: &Expr) -> Type {
+        self.type_of(condition);
+        let then_type = self.type_of(then_branch);
+        let else_type = self.type_of(else_branch);
+        if then_type == else_type { then_type } else { Type::Any }
+    }
+
+    fn visit_comma_expr(&mut self, left: &Expr, right: &Expr) -> Type {
+        self.type_of(left);
+        self.type_of(right)
+    }
+
+    fn visit_compound_assign_expr(&mut self, target: &Expr, operator: &Token, value: &Expr) -> Type {
+        let current = self.type_of(target);
+        let value = self.type_of(value);
+        let operator_type = match operator.token_type {
+            TokenType::Plus_Equal => TokenType::Plus,
+            TokenType::Minus_Equal => TokenType::Minus,
+            TokenType::Star_Equal => TokenType::Star,
+            _ => TokenType::Slash,
+        };
+        let result = self.binary_type(current, operator, &operator_type, value);
+        self.check_store(target, result, operator.line);
+        result
+    }
+
+    fn visit_prefix_update_expr(&mut self, operator: &Token, target: &Expr) -> Type {
+        let current = self.type_of(target);
+        self.expect_number(current, operator);
+        Type::Number
+    }
+
+    fn visit_postfix_update_expr(&mut self, target: &Expr, operator: &Token) -> Type {
+        self.visit_prefix_update_expr(operator, target)
+    }
+
+    fn visit_lambda_expr(&mut self, function: &Rc<FunctionDecl>) -> Type {
+        self.check_function(function);
+        Type::Function
+    }
+
+    fn visit_nil_coalesce_expr(&mut self, left: &Expr, _operator: &Token, right: &Expr) -> Type {
+        let left = self.type_of(left);
+        let right = self.type_of(right);
+        match left {
+            Type::Nil => right,
+            _ if left == right => left,
+            Type::Any => Type::Any,
+            // A non-nil left operand is always the result
+            _ => left,
+        }
+    }
+
+    fn visit_list_assign_expr(&mut self, _names: &[Token], equals: &Token, value: &Expr) -> Type {
+        let value = self.type_of(value);
+        if !Type::List.accepts(value) {
+            self.error(equals.line, format!("Cannot destructure {} as a list.", value));
+        }
+        value
+    }
+
+    fn visit_optional_get_expr(&mut self, object: &Expr, name: &Token) -> Type {
+        let object = self.type_of(object);
+        if !matches!(object, Type::Map | Type::Module | Type::Enum | Type::Nil | Type::Any) {
+            self.error(name.line, format!("Only maps, modules and enums have properties, found {}.", object));
+        }
+        Type::Any
+    }
+
+    fn visit_optional_chain_expr(&mut self, chain: &Expr) -> Type {
+        self.type_of(chain)
+    }
+}
+
+impl StmtVisitor<()> for TypeChecker {
+    fn visit_print_stmt(&mut self, expr: &Expr) {
+        self.type_of(expr);
+    }
+
+    fn visit_var_stmt(&mut self, binding: &Binding, annotation: Option<&Token>, initializer: Option<&Expr>) {
+        let actual = initializer.map(|initializer| self.type_of(initializer));
+        let name = match binding {
+            Binding::Name(name) => name,
+            Binding::List { bracket: open, names } | Binding::Map { brace: open, names } => {
+                let actual = actual.unwrap_or(Type::Nil);
+                let expected = if open.token_type == TokenType::Left_Bracket { Type::List } else { Type::Map };
+                // Map patterns also unpack a module's bindings
+                let destructurable = expected.accepts(actual) || (expected == Type::Map && actual == Type::Module);
+                if !destructurable {
+                    self.error(open.line, format!("Cannot destructure {} as a {}.", actual, expected));
+                }
+                for name in names {
+                    self.declare(name, Type::Any);
+                }
+                return;
+            }
+        };
+        // Unannotated variables may be reassigned to anything
+        let declared = self.declared_type(name, annotation, actual).unwrap_or(Type::Any);
+        self.declare(name, declared);
+    }
+
+    fn visit_const_stmt(&mut self, name: &Token, annotation: Option<&Token>, initializer: &Expr) {
+        let actual = self.type_of(initializer);
+        // A constant never changes, so its initializer's type is as good as an annotation
+        let declared = self.declared_type(name, annotation, Some(actual)).unwrap_or(actual);
+        self.declare(name, declared);
+    }
+
+    fn visit_expression_stmt(&mut self, expr: &Expr) {
+        self.type_of(expr);
+    }
+
+    fn visit_block_stmt(&mut self, statements: &[Stmt]) {
+        self.check_scoped(statements, HashMap::new());
+    }
+
+    fn visit_if_stmt(&mut self, condition: &Expr, then_branch: &Stmt, else_branch: Option<&Stmt>) {
+        self.type_of(condition);
+        then_branch.accept(self);
+        if let Some(else_branch) = else_branch {
+            else_branch.accept(self);
+        }
+    }
+
+    fn visit_while_stmt(&mut self, condition: &Expr, body: &Stmt, increment: Option<&Expr>) {
+        self.type_of(condition);
+        body.accept(self);
+        if let Some(increment) = increment {
+            self.type_of(increment);
+        }
+    }
+
+    fn visit_for_in_stmt(&mut self, name: &Token, keyword: &Token, iterable: &Expr, end: Option<&Expr>, body: &Stmt) {
+        let iterable = self.type_of(iterable);
+        let item = match end {
+            Some(end) => {
+                let end = self.type_of(end);
+                if !Type::Number.accepts(iterable) || !Type::Number.accepts(end) {
+                    self.error(keyword.line, format!("Range bounds must be numbers, found {} and {}.", iterable, end));
+                }
+                Type::Number
+            }
+            None => match iterable {
+                Type::String => Type::String,
+                Type::List | Type::Map | Type::Set | Type::Enum | Type::Any => Type::Any,
+                _ => {
+                    self.error(keyword.line, format!("Cannot iterate over {}.", iterable));
+                    Type::Any
+                }
+            },
+        };
+        let bindings = HashMap::from([(name.lexeme.clone(), item)]);
+        self.check_scoped(std::slice::from_ref(body), bindings);
+    }
+
+    fn visit_break_stmt(&mut self, _keyword: &Token) {}
+
+    fn visit_continue_stmt(&mut self, _keyword: &Token) {}
+
+    fn visit_throw_stmt(&mut self, _keyword: &Token, value: &Expr) {
+        self.type_of(value);
+    }
+
+    fn visit_assert_stmt(&mut self, _keyword: &Token, condition: &Expr, _source: &str, message: Option<&Expr>) {
+        self.type_of(condition);
+        if let Some(message) = message {
+            self.type_of(message);
+        }
+    }
+
+    fn visit_import_stmt(&mut self, _keyword: &Token, _path: &Token, alias: &Token) {
+        self.declare(alias, Type::Module);
+    }
+
+    fn visit_function_stmt(&mut self, function: &Rc<FunctionDecl>) {
+        if let Some(name) = &function.name {
+            self.declare(name, Type::Function);
+        }
+        self.check_function(function);
+    }
+
+    fn visit_return_stmt(&mut self, _keyword: &Token, value: Option<&Expr>) {
+        if let Some(value) = value {
+            self.type_of(value);
+        }
+    }
+
+    fn visit_match_stmt(&mut self, _keyword: &Token, subject: &Expr, arms: &[MatchArm]) {
+        self.type_of(subject);
+        for arm in arms {
+            arm.body.accept(self);
+        }
+    }
+
+    fn visit_enum_stmt(&mut self, name: &Token, _variants: &[Token]) {
+        self.declare(name, Type::Enum);
+    }
+
+    fn visit_try_stmt(&mut self, body: &[Stmt], catch: Option<&CatchClause>, finally: Option<&[Stmt]>) {
+        self.check_scoped(body, HashMap::new());
+        if let Some(catch) = catch {
+            let bindings = HashMap::from([(catch.name.lexeme.clone(), Type::Any)]);
+            self.check_scoped(&catch.body, bindings);
+        }
+        if let Some(finally) = finally {
+            self.check_scoped(finally, HashMap::new());
+        }
+    }
+}
@@ -1,6 +1,4 @@
-#[macro_use]
-mod macros;
-
+pub mod number;
 pub mod token;
 pub mod parser;
 pub mod interpreter;
\ No newline at end of file
@@ -0,0 +1,10 @@
+/// Format a float so it always reads as one: integral values keep a `.0` (`99.0`), so they
+/// can't be mistaken for integers, and everything else uses the shortest form that
+/// round-trips (`0.1`, `2.5`).
+pub fn format_float(value: f64) -> String {
+    if value.is_finite() && value.fract() == 0.0 {
+        format!("{:.1}", value)
+    } else {
+        value.to_string()
+    }
+}
@@ -1,4 +1,6 @@
-use crate::parser::parser::{Expr, Stmt, Literal};
+use crate::parser::parser::{Binding, Expr, FunctionDecl, Pattern, Stmt, Literal};
+use crate::number::format_float;
+use crate::token::token::Token;
 
 pub struct AstPrinter;
 
@@ -17,33 +19,217 @@ impl AstPrinter {
             Expr::Unary(operator, expr) => {
                 format!("({} {})", operator.lexeme, self.print_expr(expr))
             }
+            Expr::Interpolation(parts) => {
+                let mut result = String::from("(interpolation");
+                for part in parts {
+                    match part {
+                        Expr::Literal(Literal::String(s)) => result.push_str(&format!(" {:?}", s)),
+                        _ => result.push_str(&format!(" {}", self.print_expr(part))),
+                    }
+                }
+                result.push(')');
+                result
+            }
+            Expr::List(elements) => {
+                let mut result = String::from("(list");
+                for element in elements {
+                    result.push_str(&format!(" {}", self.print_expr(element)));
+                }
+                result.push(')');
+                result
+            }
+            Expr::Map(_, entries) => {
+                let mut result = String::from("(map");
+                for (key, value) in entries {
+                    result.push_str(&format!(" ({} {})", self.print_expr(key), self.print_expr(value)));
+                }
+                result.push(')');
+                result
+            }
+            Expr::Index(object, _, index) => {
+                format!("(index {} {})", self.print_expr(object), self.print_expr(index))
+            }
+            Expr::IndexAssign(object, _, index, value) => {
+                format!(
+                    "(index-assign {} {} {})",
+                    self.print_expr(object),
+                    self.print_expr(index),
+                    self.print_expr(value)
+                )
+            }
+            Expr::Conditional(condition, then_branch, else_branch) => {
+                format!(
+                    "(?: {} {} {})",
+                    self.print_expr(condition),
+                    self.print_expr(then_branch),
+                    self.print_expr(else_branch)
+                )
+            }
+            Expr::Comma(left, right) => {
+                format!("(, {} {})", self.print_expr(left), self.print_expr(right))
+            }
+            Expr::CompoundAssign(target, operator, value) => {
+                format!("({} {} {})", operator.lexeme, self.print_expr(target), self.print_expr(value))
+            }
+            Expr::PrefixUpdate(operator, target) => {
+                format!("(prefix {} {})", operator.lexeme, self.print_expr(target))
+            }
+            Expr::PostfixUpdate(target, operator) => {
+                format!("(postfix {} {})", operator.lexeme, self.print_expr(target))
+            }
+            Expr::Get(object, name) => format!("(get {} {})", self.print_expr(object), name.lexeme),
+            Expr::OptionalGet(object, name) => format!("(?. {} {})", self.print_expr(object), name.lexeme),
+            // The `?.` nodes inside already show where the chain can stop
+            Expr::OptionalChain(chain) => self.print_expr(chain),
+            Expr::ListAssign(names, _, value) => {
+                format!("(= [{}] {})", self.print_names(names), self.print_expr(value))
+            }
+            Expr::NilCoalesce(left, _, right) => {
+                format!("(?? {} {})", self.print_expr(left), self.print_expr(right))
+            }
+            Expr::Lambda(function) => self.print_function(function),
+            Expr::Call(callee, _, arguments) => {
+                let mut result = format!("(call {}", self.print_expr(callee));
+                for argument in arguments {
+                    result.push_str(&format!(" {}", self.print_expr(argument)));
+                }
+                result.push(')');
+                result
+            }
         }
     }
 
     fn print_literal(&self, literal: &Literal) -> String {
         match literal {
             Literal::String(s) => s.clone(),
-            Literal::Number(n) => format_number_test!(n),
+            Literal::Number(n) => format_float(*n),
+            // Printed like the float it equals, keeping the `parse` output unchanged
+            Literal::Integer(n) => format!("{}.0", n),
             Literal::Bool(b) => format!("{}", b),
             Literal::Nil => "nil".to_string(),
         }
     }
 
+    fn print_names(&self, names: &[Token]) -> String {
+        names.iter().map(|name| name.lexeme.as_str()).collect::<Vec<_>>().join(" ")
+    }
+
+    // `name` or `name:type`
+    fn print_declared_name(&self, name: &Token, annotation: Option<&Token>) -> String {
+        match annotation {
+            Some(annotation) => format!("{}:{}", name.lexeme, annotation.lexeme),
+            None => name.lexeme.clone(),
+        }
+    }
+
+    fn print_pattern(&self, pattern: &Pattern) -> String {
+        match pattern {
+            Pattern::Literal(literal) => self.print_literal(literal),
+            Pattern::Range(low, high) => format!("{}..{}", self.print_literal(low), self.print_literal(high)),
+            Pattern::Variant { enum_name, variant } => format!("{}.{}", enum_name.lexeme, variant.lexeme),
+            Pattern::Wildcard => "_".to_string(),
+        }
+    }
+
+    // (fun [name] (params...) (block ...))
+    fn print_function(&self, function: &FunctionDecl) -> String {
+        let params: Vec<&str> = function.params.iter().map(|param| param.lexeme.as_str()).collect();
+        let name = function
+            .name
+            .as_ref()
+            .map(|name| format!("{} ", name.lexeme))
+            .unwrap_or_default();
+        format!("(fun {}({}) {})", name, params.join(" "), self.print_block(&function.body))
+    }
+
+    fn print_block(&self, statements: &[Stmt]) -> String {
+        let mut result = String::from("(block");
+        for statement in statements {
+            result.push_str(&format!(" {}", self.print_stmt(statement)));
+        }
+        result.push(')');
+        result
+    }
+
     pub fn print_stmt(&self, stmt: &Stmt) -> String {
         match stmt {
             Stmt::Expression(expr) => self.print_expr(expr),
             Stmt::Print(expr) => format!("(print {})", self.print_expr(expr)),
-            Stmt::Var { name, initializer } => {
+            Stmt::Var { binding, annotation, initializer } => {
                 let init = initializer
                     .as_ref()
                     .map(|init| self.print_expr(init))
                     .unwrap_or_else(|| "nil".to_string());
-                format!("(var {} {})", name.lexeme, init)
+                let target = match binding {
+                    Binding::Name(name) => self.print_declared_name(name, annotation.as_ref()),
+                    Binding::List { names, .. } => format!("[{}]", self.print_names(names)),
+                    Binding::Map { names, .. } => format!("{{{}}}", self.print_names(names)),
+                };
+                format!("(var {} {})", target, init)
+            }
+            Stmt::Const { name, annotation, initializer } => {
+                format!(
+                    "(const {} {})",
+                    self.print_declared_name(name, annotation.as_ref()),
+                    self.print_expr(initializer)
+                )
+            }
+            Stmt::Block(statements) => self.print_block(statements),
+            Stmt::If { condition, then_branch, else_branch } => match else_branch {
+                Some(else_branch) => format!(
+                    "(if {} {} {})",
+                    self.print_expr(condition),
+                    self.print_stmt(then_branch),
+                    self.print_stmt(else_branch)
+                ),
+                None => format!("(if {} {})", self.print_expr(condition), self.print_stmt(then_branch)),
+            },
+            Stmt::While { condition, body, increment } => match increment {
+                Some(increment) => format!(
+                    "(while {} {} {})",
+                    self.print_expr(condition),
+                    self.print_stmt(body),
+                    self.print_expr(increment)
+                ),
+                None => format!("(while {} {})", self.print_expr(condition), self.print_stmt(body)),
+            },
+            Stmt::ForIn { name, iterable, end, body, .. } => {
+                let iterable = match end {
+                    Some(end) => format!("(.. {} {})", self.print_expr(iterable), self.print_expr(end)),
+                    None => self.print_expr(iterable),
+                };
+                format!("(for {} in {} {})", name.lexeme, iterable, self.print_stmt(body))
             }
-            Stmt::Block(statements) => {
-                let mut result = String::from("(block");
-                for statement in statements {
-                    result.push_str(&format!(" {}", self.print_stmt(statement)));
+            Stmt::Break(_) => "(break)".to_string(),
+            Stmt::Continue(_) => "(continue)".to_string(),
+            Stmt::Import { path, alias, .. } => format!("(import {} {})", path.lexeme, alias.lexeme),
+            Stmt::Function(function) => self.print_function(function),
+            Stmt::Return { value, .. } => match value {
+                Some(value) => format!("(return {})", self.print_expr(value)),
+                None => "(return)".to_string(),
+            },
+            Stmt::Match { subject, arms, .. } => {
+                let mut result = format!("(match {}", self.print_expr(subject));
+                for arm in arms {
+                    let patterns: Vec<String> = arm.patterns.iter().map(|p| self.print_pattern(p)).collect();
+                    result.push_str(&format!(" ({} => {})", patterns.join(" | "), self.print_stmt(&arm.body)));
+                }
+                result.push(')');
+                result
+            }
+            Stmt::Enum { name, variants } => format!("(enum {} {})", name.lexeme, self.print_names(variants)),
+            Stmt::Throw { value, .. } => format!("(throw {})", self.print_expr(value)),
+            Stmt::Assert { condition, message, .. } => match message {
+                Some(message) => format!("(assert {} {})", self.print_expr(condition), self.print_expr(message)),
+                None => format!("(assert {})", self.print_expr(condition)),
+            },
+            Stmt::Try { body, catch, finally } => {
+                let mut result = format!("(try {}", self.print_block(body));
+                if let Some(catch) = catch {
+                    result.push_str(&format!(" (catch {} {})", catch.name.lexeme, self.print_block(&catch.body)));
+                }
+                if let Some(finally) = finally {
+                    result.push_str(&format!(" (finally {})", self.print_block(finally)));
                 }
                 result.push(')');
                 result
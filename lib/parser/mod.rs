@@ -1,5 +1,6 @@
+#[allow(clippy::module_inception)]
 pub mod parser;
 pub mod ast_printer;
 
-pub use parser::{Expr, Stmt, Parser, Literal, ParseError};
+pub use parser::{Binding, Expr, Stmt, Parser, Literal, ParseError, CatchClause, FunctionDecl, MatchArm, Pattern};
 pub use ast_printer::AstPrinter;
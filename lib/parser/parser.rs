@@ -1,11 +1,13 @@
-use crate::token::token::{ Token, TokenType };
+use crate::token::token::{ Token, TokenType, Literal as TokenLiteral };
 use std::fmt;
 use std::error::Error;
+use std::rc::Rc;
 
 #[derive(Debug)]
 pub enum Literal {
     String(String),
     Number(f64),
+    Integer(i64),
     Nil,
     Bool(bool),
 }
@@ -18,22 +20,115 @@ pub enum Expr {
     Binary(Box<Expr>, Token, Box<Expr>),
     Grouping(Box<Expr>),
     Unary(Token, Box<Expr>),
+    Interpolation(Vec<Expr>),
+    List(Vec<Expr>),
+    Map(Token, Vec<(Expr, Expr)>),
+    Index(Box<Expr>, Token, Box<Expr>),
+    IndexAssign(Box<Expr>, Token, Box<Expr>, Box<Expr>),
+    Call(Box<Expr>, Token, Vec<Expr>),
+    Get(Box<Expr>, Token),
+    Conditional(Box<Expr>, Box<Expr>, Box<Expr>),
+    Comma(Box<Expr>, Box<Expr>),
+    CompoundAssign(Box<Expr>, Token, Box<Expr>),
+    PrefixUpdate(Token, Box<Expr>),
+    PostfixUpdate(Box<Expr>, Token),
+    Lambda(Rc<FunctionDecl>),
+    NilCoalesce(Box<Expr>, Token, Box<Expr>),
+    // `[a, b] = value`: the target names, the `=` token and the list being unpacked
+    ListAssign(Vec<Token>, Token, Box<Expr>),
+    OptionalGet(Box<Expr>, Token),
+    // A postfix chain containing `?.`; a nil receiver there skips the rest of the chain
+    OptionalChain(Box<Expr>),
 }
 
 #[derive(Debug)]
 pub enum Stmt {
     Expression(Expr),
     Print(Expr),
-    Var { name: Token, initializer: Option<Expr> },
+    // `annotation` is the optional type name after `:`, checked only by the `typecheck` command
+    Var { binding: Binding, annotation: Option<Token>, initializer: Option<Expr> },
+    Const { name: Token, annotation: Option<Token>, initializer: Expr },
     Block(Vec<Stmt>),
+    If { condition: Expr, then_branch: Box<Stmt>, else_branch: Option<Box<Stmt>> },
+    // `increment` is the third clause of a desugared `for`; it also runs after `continue`
+    While { condition: Expr, body: Box<Stmt>, increment: Option<Expr> },
+    // `for (name in iterable)`; `end` is set for a half-open numeric range `for (i in start..end)`
+    ForIn { name: Token, keyword: Token, iterable: Expr, end: Option<Expr>, body: Box<Stmt> },
+    Break(Token),
+    Continue(Token),
+    Throw { keyword: Token, value: Expr },
+    // `source` is the condition as written, for the failure message
+    Assert { keyword: Token, condition: Expr, source: String, message: Option<Expr> },
+    Try { body: Vec<Stmt>, catch: Option<CatchClause>, finally: Option<Vec<Stmt>> },
+    Import { keyword: Token, path: Token, alias: Token },
+    Function(Rc<FunctionDecl>),
+    Return { keyword: Token, value: Option<Expr> },
+    Match { keyword: Token, subject: Expr, arms: Vec<MatchArm> },
+    Enum { name: Token, variants: Vec<Token> },
+}
+
+/// The names a `var` declaration introduces: one name, or a destructuring pattern
+/// that unpacks a list (`var [a, b]`) or a map's string keys (`var {x, y}`).
+#[derive(Debug)]
+pub enum Binding {
+    Name(Token),
+    List { bracket: Token, names: Vec<Token> },
+    Map { brace: Token, names: Vec<Token> },
+}
+
+impl Binding {
+    pub fn names(&self) -> &[Token] {
+        match self {
+            Binding::Name(name) => std::slice::from_ref(name),
+            Binding::List { names, .. } | Binding::Map { names, .. } => names,
+        }
+    }
+}
+
+/// One `pattern | pattern => body` arm of a `match` statement.
+#[derive(Debug)]
+pub struct MatchArm {
+    pub patterns: Vec<Pattern>,
+    // The `=>` token, which locates the arm in warnings
+    pub arrow: Token,
+    pub body: Box<Stmt>,
+}
+
+#[derive(Debug)]
+pub enum Pattern {
+    Literal(Literal),
+    // Half-open numeric range: `0..10` matches 0 up to but not including 10
+    Range(Literal, Literal),
+    // An enum variant such as `Color.Red`, looked up when the arm is tried
+    Variant { enum_name: Token, variant: Token },
+    Wildcard,
+}
+
+#[derive(Debug)]
+pub struct CatchClause {
+    pub name: Token,
+    pub body: Vec<Stmt>,
+}
+
+/// A function's parameters and body, shared by the AST and every function value made from it.
+#[derive(Debug)]
+pub struct FunctionDecl {
+    // The `fun` keyword; its line identifies anonymous functions in error messages
+    pub keyword: Token,
+    pub name: Option<Token>,
+    pub params: Vec<Token>,
+    pub body: Vec<Stmt>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct ParseError;
+pub struct ParseError {
+    pub message: String,
+    pub line: usize,
+}
 
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Parse Error")
+        write!(f, "Error on line {}: {}", self.line, self.message)
     }
 }
 
@@ -41,13 +136,17 @@ impl Error for ParseError {}
 
 pub struct Parser {
     tokens: Vec<Token>,
+    // The text the tokens were scanned from, for quoting source in messages
+    source: String,
     current: usize,
+    loop_depth: usize,
+    function_depth: usize,
     pub require_semicolon: bool,
 }
 
 impl Parser {
-    pub fn new(tokens: Vec<Token>, require_semicolon:bool) -> Self {
-        Parser { tokens, current: 0, require_semicolon }
+    pub fn new(tokens: Vec<Token>, source: &str, require_semicolon:bool) -> Self {
+        Parser { tokens, source: source.to_string(), current: 0, loop_depth: 0, function_depth: 0, require_semicolon }
     }
 
     fn advance(&mut self) -> &Token {
@@ -86,37 +185,121 @@ impl Parser {
         self.peek().token_type == token_type
     }
 
+    // Whether the token after the current one has the given type
+    fn check_next(&self, token_type: TokenType) -> bool {
+        self.tokens
+            .get(self.current + 1)
+            .is_some_and(|token| token.token_type == token_type)
+    }
+
     fn consume(&mut self, token_type: TokenType, message: &str) -> Result<&Token, ParseError> {
         if self.check(token_type) {
-            return Ok(self.advance());
+            Ok(self.advance())
         } else {
             let token = self.peek();
-            return Err(self.error(token, &format!("{} at line {}", message, token.line)));
+            Err(self.error(token, &format!("{} at line {}", message, token.line)))
         }
     }
 
     fn error(&self, token: &Token, message: &str) -> ParseError {
-        eprintln!("Error on line {}: {}", token.line, message);
-        ParseError
+        ParseError {
+            message: message.to_string(),
+            line: token.line,
+        }
     }
 
     fn expression(&mut self) -> Result<Expr, ParseError> {
-        self.assignment()
+        self.comma()
+    }
+
+    // Lowest precedence: evaluate both sides, yield the right one. Argument lists and
+    // collection literals parse their elements with `assignment` so commas still separate them.
+    fn comma(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.assignment()?;
+
+        while self.match_token(&[TokenType::Comma]) {
+            let right = self.assignment()?;
+            expr = Expr::Comma(Box::new(expr), Box::new(right));
+        }
+
+        Ok(expr)
     }
 
     fn assignment(&mut self) -> Result<Expr, ParseError> {
-        let expr = self.or()?;
+        let expr = self.conditional()?;
 
         if self.match_token(&[TokenType::Equal]) {
             let equals = self.previous().clone();
             let value = self.assignment()?;
 
-            if let Expr::Variable(var) = expr {
-                return Ok(Expr::Assign(var, Box::new(value)));
-            } else {
-                return Err(self.error(&equals, "Invalid assignment target."));
-            }
+            return match expr {
+                Expr::Variable(var) => Ok(Expr::Assign(var, Box::new(value))),
+                Expr::Index(object, bracket, index) => {
+                    Ok(Expr::IndexAssign(object, bracket, index, Box::new(value)))
+                }
+                // `[a, b] = [b, a]` reads the left side as a list literal of variables
+                Expr::List(elements) => {
+                    let mut names = Vec::new();
+                    for element in elements {
+                        match element {
+                            Expr::Variable(name) => names.push(name),
+                            _ => return Err(self.error(&equals, "Invalid destructuring assignment target.")),
+                        }
+                    }
+                    Ok(Expr::ListAssign(names, equals, Box::new(value)))
+                }
+                _ => Err(self.error(&equals, "Invalid assignment target.")),
+            };
         }
+
+        if self.match_token(&[
+            TokenType::Plus_Equal, TokenType::Minus_Equal,
+            TokenType::Star_Equal, TokenType::Slash_Equal,
+        ]) {
+            let operator = self.previous().clone();
+            let value = self.assignment()?;
+            let target = self.assignment_target(expr, &operator)?;
+            return Ok(Expr::CompoundAssign(Box::new(target), operator, Box::new(value)));
+        }
+        Ok(expr)
+    }
+
+    // Targets of `+=`-style and `++`/`--` operators follow the same rules as `=`
+    fn assignment_target(&self, expr: Expr, operator: &Token) -> Result<Expr, ParseError> {
+        match expr {
+            Expr::Variable(_) | Expr::Index(..) => Ok(expr),
+            _ => Err(self.error(operator, "Invalid assignment target.")),
+        }
+    }
+
+    fn conditional(&mut self) -> Result<Expr, ParseError> {
+        let condition = self.nil_coalesce()?;
+
+        if self.match_token(&[TokenType::Question]) {
+            let then_branch = self.expression()?;
+            self.consume(TokenType::Colon, "Expect ':' after then branch of conditional expression.")?;
+            // Right-associative: a ? b : c ? d : e groups as a ? b : (c ? d : e)
+            let else_branch = self.conditional()?;
+            return Ok(Expr::Conditional(
+                Box::new(condition),
+                Box::new(then_branch),
+                Box::new(else_branch),
+            ));
+        }
+
+        Ok(condition)
+    }
+
+    // `a ?? b` sits just below `or`, so `a or b ?? c` groups as `(a or b) ?? c`
+    fn nil_coalesce(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.or()?;
+
+        while self.match_token(&[TokenType::Question_Question]) {
+            let operator = self.previous().clone();
+            let right = self.or()?;
+            expr = Expr::NilCoalesce(Box::new(expr), operator, Box::new(right));
+        }
+
         Ok(expr)
     }
 
@@ -145,9 +328,45 @@ impl Parser {
     }
 
     fn equality(&mut self) -> Result<Expr, ParseError> {
-        let mut expr = self.comparison()?;
+        let mut expr = self.bitwise_or()?;
 
         while self.match_token(&[TokenType::Equal_Equal, TokenType::Bang_Equal]) {
+            let operator = self.previous().clone();
+            let right = self.bitwise_or()?;
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(right));
+        }
+
+        Ok(expr)
+    }
+
+    fn bitwise_or(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.bitwise_xor()?;
+
+        while self.match_token(&[TokenType::Pipe]) {
+            let operator = self.previous().clone();
+            let right = self.bitwise_xor()?;
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(right));
+        }
+
+        Ok(expr)
+    }
+
+    fn bitwise_xor(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.bitwise_and()?;
+
+        while self.match_token(&[TokenType::Caret]) {
+            let operator = self.previous().clone();
+            let right = self.bitwise_and()?;
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(right));
+        }
+
+        Ok(expr)
+    }
+
+    fn bitwise_and(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.comparison()?;
+
+        while self.match_token(&[TokenType::Ampersand]) {
             let operator = self.previous().clone();
             let right = self.comparison()?;
             expr = Expr::Binary(Box::new(expr), operator, Box::new(right));
@@ -157,12 +376,24 @@ impl Parser {
     }
 
     fn comparison(&mut self) -> Result<Expr, ParseError> {
-        let mut expr = self.term()?;
+        let mut expr = self.shift()?;
 
         while self.match_token(&[
             TokenType::Greater, TokenType::Greater_Equal,
             TokenType::Less, TokenType::Less_Equal,
         ]) {
+            let operator = self.previous().clone();
+            let right = self.shift()?;
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(right));
+        }
+
+        Ok(expr)
+    }
+
+    fn shift(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.term()?;
+
+        while self.match_token(&[TokenType::Less_Less, TokenType::Greater_Greater]) {
             let operator = self.previous().clone();
             let right = self.term()?;
             expr = Expr::Binary(Box::new(expr), operator, Box::new(right));
@@ -186,7 +417,10 @@ impl Parser {
     fn factor(&mut self) -> Result<Expr, ParseError> {
         let mut expr = self.unary()?;
 
-        while self.match_token(&[TokenType::Star, TokenType::Slash]) {
+        while self.match_token(&[
+            TokenType::Star, TokenType::Slash,
+            TokenType::Percent, TokenType::Tilde_Slash,
+        ]) {
             let operator = self.previous().clone();
             let right = self.unary()?;
             expr = Expr::Binary(Box::new(expr), operator, Box::new(right));
@@ -196,13 +430,102 @@ impl Parser {
     }
 
     fn unary(&mut self) -> Result<Expr, ParseError> {
-        if self.match_token(&[TokenType::Bang, TokenType::Minus]) {
+        if self.match_token(&[TokenType::Bang, TokenType::Minus, TokenType::Tilde]) {
             let operator = self.previous().clone();
             let right = self.unary()?;
             return Ok(Expr::Unary(operator, Box::new(right)));
         }
+        if self.match_token(&[TokenType::Plus_Plus, TokenType::Minus_Minus]) {
+            let operator = self.previous().clone();
+            let operand = self.unary()?;
+            let target = self.assignment_target(operand, &operator)?;
+            return Ok(Expr::PrefixUpdate(operator, Box::new(target)));
+        }
+
+        self.power()
+    }
+
+    // `**` binds tighter than unary minus and is right-associative: -2 ** 2 is -(2 ** 2)
+    fn power(&mut self) -> Result<Expr, ParseError> {
+        let expr = self.postfix()?;
+
+        if self.match_token(&[TokenType::Star_Star]) {
+            let operator = self.previous().clone();
+            let right = self.unary()?;
+            return Ok(Expr::Binary(Box::new(expr), operator, Box::new(right)));
+        }
+
+        Ok(expr)
+    }
+
+    fn postfix(&mut self) -> Result<Expr, ParseError> {
+        let expr = self.call()?;
+
+        if self.match_token(&[TokenType::Plus_Plus, TokenType::Minus_Minus]) {
+            let operator = self.previous().clone();
+            let target = self.assignment_target(expr, &operator)?;
+            return Ok(Expr::PostfixUpdate(Box::new(target), operator));
+        }
+
+        Ok(expr)
+    }
+
+    fn call(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.primary()?;
+        let mut optional = false;
+
+        loop {
+            if self.match_token(&[TokenType::Left_Paren]) {
+                expr = self.finish_call(expr)?;
+            } else if self.match_token(&[TokenType::Left_Bracket]) {
+                let bracket = self.previous().clone();
+                let index = self.expression()?;
+                self.consume(TokenType::Right_Bracket, "Expect ']' after index.")?;
+                expr = Expr::Index(Box::new(expr), bracket, Box::new(index));
+            } else if self.match_token(&[TokenType::Dot]) {
+                let name = self.consume(TokenType::Identifier, "Expect property name after '.'.")?.clone();
+                expr = Expr::Get(Box::new(expr), name);
+            } else if self.match_token(&[TokenType::Question_Dot]) {
+                let name = self.consume(TokenType::Identifier, "Expect property name after '?.'.")?.clone();
+                expr = Expr::OptionalGet(Box::new(expr), name);
+                optional = true;
+            } else {
+                break;
+            }
+        }
+
+        Ok(if optional { Expr::OptionalChain(Box::new(expr)) } else { expr })
+    }
+
+    fn finish_call(&mut self, callee: Expr) -> Result<Expr, ParseError> {
+        let mut arguments = Vec::new();
+        if !self.check(TokenType::Right_Paren) {
+            loop {
+                arguments.push(self.assignment()?);
+                if !self.match_token(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+
+        let paren = self.consume(TokenType::Right_Paren, "Expect ')' after arguments.")?.clone();
+        Ok(Expr::Call(Box::new(callee), paren, arguments))
+    }
+
+    fn list(&mut self) -> Result<Expr, ParseError> {
+        let mut elements = Vec::new();
+        if !self.check(TokenType::Right_Bracket) {
+            loop {
+                elements.push(self.assignment()?);
+                // Allow a trailing comma before the closing bracket
+                if !self.match_token(&[TokenType::Comma]) || self.check(TokenType::Right_Bracket) {
+                    break;
+                }
+            }
+        }
 
-        self.primary()
+        self.consume(TokenType::Right_Bracket, "Expect ']' after list elements.")?;
+        Ok(Expr::List(elements))
     }
 
     fn primary(&mut self) -> Result<Expr, ParseError> {
@@ -216,51 +539,521 @@ impl Parser {
             return Ok(Expr::Literal(Literal::Nil));
         }
         if self.match_token(&[TokenType::Number]) {
-            let lexeme = self.previous().lexeme.clone();
-            let value: f64 = lexeme.parse().unwrap();
-            return Ok(Expr::Literal(Literal::Number(value)));
+            match self.previous().literal {
+                TokenLiteral::Num(value) => return Ok(Expr::Literal(Literal::Number(value))),
+                TokenLiteral::Int(value) => return Ok(Expr::Literal(Literal::Integer(value))),
+                _ => {}
+            }
+            return Err(self.error(self.previous(), "Expect number literal."));
         }
         if self.match_token(&[TokenType::String]) {
-            let lexeme = self.previous().lexeme.clone();
-            let value = lexeme.trim_matches('"').to_string();
-            return Ok(Expr::Literal(Literal::String(value)));
+            return self.string_segment();
+        }
+        if self.match_token(&[TokenType::String_Head]) {
+            return self.interpolation();
         }
         if self.match_token(&[TokenType::Identifier]) {
             return Ok(Expr::Variable(self.previous().clone()));
         }
+        if self.match_token(&[TokenType::Fun]) {
+            let keyword = self.previous().clone();
+            return Ok(Expr::Lambda(self.function(keyword, None)?));
+        }
         if self.match_token(&[TokenType::Left_Paren]) {
             let expr = self.expression()?;
             self.consume(TokenType::Right_Paren, "Expect ')' after expression.")?;
             return Ok(Expr::Grouping(Box::new(expr)));
         }
+        if self.match_token(&[TokenType::Left_Bracket]) {
+            return self.list();
+        }
+        // Statements starting with `{` are blocks, so a brace here must open a map literal
+        if self.match_token(&[TokenType::Left_Brace]) {
+            return self.map();
+        }
 
         Err(self.error(self.peek(), "Expect expression."))
     }
 
+    fn map(&mut self) -> Result<Expr, ParseError> {
+        let brace = self.previous().clone();
+        let mut entries = Vec::new();
+        if !self.check(TokenType::Right_Brace) {
+            loop {
+                let key = self.assignment()?;
+                self.consume(TokenType::Colon, "Expect ':' after map key.")?;
+                let value = self.assignment()?;
+                entries.push((key, value));
+                // Allow a trailing comma before the closing brace
+                if !self.match_token(&[TokenType::Comma]) || self.check(TokenType::Right_Brace) {
+                    break;
+                }
+            }
+        }
+
+        self.consume(TokenType::Right_Brace, "Expect '}' after map entries.")?;
+        Ok(Expr::Map(brace, entries))
+    }
+
+    fn string_segment(&self) -> Result<Expr, ParseError> {
+        if let TokenLiteral::Str(value) = &self.previous().literal {
+            return Ok(Expr::Literal(Literal::String(value.clone())));
+        }
+        Err(self.error(self.previous(), "Expect string literal."))
+    }
+
+    fn interpolation(&mut self) -> Result<Expr, ParseError> {
+        let mut parts = vec![self.string_segment()?];
+
+        loop {
+            parts.push(self.expression()?);
+            if self.match_token(&[TokenType::String_Middle]) {
+                parts.push(self.string_segment()?);
+            } else {
+                self.consume(TokenType::String_Tail, "Expect '}' after interpolated expression.")?;
+                parts.push(self.string_segment()?);
+                break;
+            }
+        }
+
+        Ok(Expr::Interpolation(parts))
+    }
+
+    // Parameters and body of a function; `fun` and any name have already been consumed
+    fn function(&mut self, keyword: Token, name: Option<Token>) -> Result<Rc<FunctionDecl>, ParseError> {
+        self.consume(TokenType::Left_Paren, "Expect '(' before parameters.")?;
+        let mut params = Vec::new();
+        if !self.check(TokenType::Right_Paren) {
+            loop {
+                params.push(self.consume(TokenType::Identifier, "Expect parameter name.")?.clone());
+                if !self.match_token(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+        self.consume(TokenType::Right_Paren, "Expect ')' after parameters.")?;
+        self.consume(TokenType::Left_Brace, "Expect '{' before function body.")?;
+
+        // `break` and `continue` can't jump out of a function into a loop around it
+        let loop_depth = std::mem::replace(&mut self.loop_depth, 0);
+        self.function_depth += 1;
+        let body = self.block();
+        self.function_depth -= 1;
+        self.loop_depth = loop_depth;
+
+        Ok(Rc::new(FunctionDecl { keyword, name, params, body: body? }))
+    }
+
+    fn function_declaration(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous().clone();
+        let name = self.consume(TokenType::Identifier, "Expect function name.")?.clone();
+        Ok(Stmt::Function(self.function(keyword, Some(name))?))
+    }
+
+    fn return_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous().clone();
+        if self.function_depth == 0 {
+            return Err(self.error(&keyword, "Can't return from top-level code."));
+        }
+
+        let value = if self.check(TokenType::Semicolon) || self.check(TokenType::Right_Brace) || self.is_at_end() {
+            None
+        } else {
+            Some(self.expression()?)
+        };
+        if self.require_semicolon {
+            self.consume(TokenType::Semicolon, "Expect ';' after return value.")?;
+        }
+        Ok(Stmt::Return { keyword, value })
+    }
+
+    fn match_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous().clone();
+        self.consume(TokenType::Left_Paren, "Expect '(' after 'match'.")?;
+        let subject = self.expression()?;
+        self.consume(TokenType::Right_Paren, "Expect ')' after match subject.")?;
+        self.consume(TokenType::Left_Brace, "Expect '{' before match arms.")?;
+
+        let mut arms = Vec::new();
+        while !self.check(TokenType::Right_Brace) && !self.is_at_end() {
+            let mut patterns = vec![self.pattern()?];
+            while self.match_token(&[TokenType::Pipe]) {
+                patterns.push(self.pattern()?);
+            }
+            let arrow = self.consume(TokenType::Fat_Arrow, "Expect '=>' after match pattern.")?.clone();
+            let body = Box::new(self.match_arm_body()?);
+            arms.push(MatchArm { patterns, arrow, body });
+        }
+
+        self.consume(TokenType::Right_Brace, "Expect '}' after match arms.")?;
+        Ok(Stmt::Match { keyword, subject, arms })
+    }
+
+    fn match_arm_body(&mut self) -> Result<Stmt, ParseError> {
+        if self.match_token(&[TokenType::Left_Brace]) {
+            let body = Stmt::Block(self.block()?);
+            self.match_token(&[TokenType::Comma]);
+            return Ok(body);
+        }
+
+        // Without braces the body is a `print` or an expression ended by `,` or `;`,
+        // so a comma here separates arms rather than acting as the comma operator
+        let body = if self.match_token(&[TokenType::Print]) {
+            Stmt::Print(self.assignment()?)
+        } else {
+            Stmt::Expression(self.assignment()?)
+        };
+        if !self.match_token(&[TokenType::Comma, TokenType::Semicolon]) && !self.check(TokenType::Right_Brace) {
+            return Err(self.error(self.peek(), "Expect ',' after match arm."));
+        }
+        Ok(body)
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, ParseError> {
+        if self.check(TokenType::Identifier) && self.peek().lexeme == "_" {
+            self.advance();
+            return Ok(Pattern::Wildcard);
+        }
+        if self.check(TokenType::Identifier) && self.check_next(TokenType::Dot) {
+            let enum_name = self.advance().clone();
+            self.advance();
+            let variant = self.consume(TokenType::Identifier, "Expect variant name after '.'.")?.clone();
+            return Ok(Pattern::Variant { enum_name, variant });
+        }
+        if self.check(TokenType::Minus) || self.check(TokenType::Number) {
+            let low = self.pattern_number()?;
+            if self.match_token(&[TokenType::Dot_Dot]) {
+                return Ok(Pattern::Range(low, self.pattern_number()?));
+            }
+            return Ok(Pattern::Literal(low));
+        }
+        if self.match_token(&[TokenType::String]) {
+            if let TokenLiteral::Str(value) = &self.previous().literal {
+                return Ok(Pattern::Literal(Literal::String(value.clone())));
+            }
+        }
+        if self.match_token(&[TokenType::True]) {
+            return Ok(Pattern::Literal(Literal::Bool(true)));
+        }
+        if self.match_token(&[TokenType::False]) {
+            return Ok(Pattern::Literal(Literal::Bool(false)));
+        }
+        if self.match_token(&[TokenType::Nil]) {
+            return Ok(Pattern::Literal(Literal::Nil));
+        }
+
+        Err(self.error(self.peek(), "Expect literal, range, enum variant or '_' pattern."))
+    }
+
+    // A number literal in a pattern, optionally negated
+    fn pattern_number(&mut self) -> Result<Literal, ParseError> {
+        let negate = self.match_token(&[TokenType::Minus]);
+        let token = self.consume(TokenType::Number, "Expect number in pattern.")?;
+        match token.literal {
+            TokenLiteral::Num(value) => Ok(Literal::Number(if negate { -value } else { value })),
+            TokenLiteral::Int(value) => Ok(Literal::Integer(if negate { -value } else { value })),
+            _ => Err(self.error(self.previous(), "Expect number literal.")),
+        }
+    }
+
     fn var_declaration(&mut self) -> Result<Stmt, ParseError> {
+        let declaration = self.var_binding()?;
+
+        if self.require_semicolon {
+            self.consume(TokenType::Semicolon, "Expect ';' after variable declaration.")?;
+        }
+        Ok(declaration)
+    }
+
+    // The part of a `var` declaration before its `;`, shared with `for` initializers
+    fn var_binding(&mut self) -> Result<Stmt, ParseError> {
+        if self.match_token(&[TokenType::Left_Bracket, TokenType::Left_Brace]) {
+            let binding = self.destructuring_pattern()?;
+            self.consume(TokenType::Equal, "Expect '=' after destructuring pattern.")?;
+            let initializer = self.expression()?;
+            return Ok(Stmt::Var { binding, annotation: None, initializer: Some(initializer) });
+        }
+
         let name = self.consume(TokenType::Identifier, "Expect variable name.")?.clone();
+        let annotation = self.type_annotation()?;
 
         let mut initializer = None;
         if self.match_token(&[TokenType::Equal]) {
             initializer = Some(self.expression()?);
         }
 
+        Ok(Stmt::Var { binding: Binding::Name(name), annotation, initializer })
+    }
+
+    // `[a, b]` or `{x, y}` after `var`; the opening bracket has been consumed
+    fn destructuring_pattern(&mut self) -> Result<Binding, ParseError> {
+        let open = self.previous().clone();
+        let is_list = open.token_type == TokenType::Left_Bracket;
+
+        let mut names = Vec::new();
+        loop {
+            names.push(self.consume(TokenType::Identifier, "Expect variable name in destructuring pattern.")?.clone());
+            if !self.match_token(&[TokenType::Comma]) {
+                break;
+            }
+        }
+
+        if is_list {
+            self.consume(TokenType::Right_Bracket, "Expect ']' after destructured names.")?;
+            Ok(Binding::List { bracket: open, names })
+        } else {
+            self.consume(TokenType::Right_Brace, "Expect '}' after destructured names.")?;
+            Ok(Binding::Map { brace: open, names })
+        }
+    }
+
+    // An optional `: type` after a declared name
+    fn type_annotation(&mut self) -> Result<Option<Token>, ParseError> {
+        if !self.match_token(&[TokenType::Colon]) {
+            return Ok(None);
+        }
+        // `nil` is a keyword, but also the name of its own type
+        if self.match_token(&[TokenType::Identifier, TokenType::Nil]) {
+            return Ok(Some(self.previous().clone()));
+        }
+        Err(self.error(self.peek(), "Expect type name after ':'."))
+    }
+
+    fn const_declaration(&mut self) -> Result<Stmt, ParseError> {
+        let name = self.consume(TokenType::Identifier, "Expect constant name.")?.clone();
+        let annotation = self.type_annotation()?;
+        self.consume(TokenType::Equal, "Expect '=' after constant name; constants must be initialized.")?;
+        let initializer = self.expression()?;
+
         if self.require_semicolon {
-            self.consume(TokenType::Semicolon, "Expect ';' after variable declaration.")?;
+            self.consume(TokenType::Semicolon, "Expect ';' after constant declaration.")?;
+        }
+        Ok(Stmt::Const { name, annotation, initializer })
+    }
+
+    fn import_declaration(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous().clone();
+        let path = self.consume(TokenType::String, "Expect module path string after 'import'.")?.clone();
+        self.consume(TokenType::As, "Expect 'as' after module path.")?;
+        let alias = self.consume(TokenType::Identifier, "Expect module name after 'as'.")?.clone();
+
+        if self.require_semicolon {
+            self.consume(TokenType::Semicolon, "Expect ';' after import.")?;
+        }
+        Ok(Stmt::Import { keyword, path, alias })
+    }
+
+    fn enum_declaration(&mut self) -> Result<Stmt, ParseError> {
+        let name = self.consume(TokenType::Identifier, "Expect enum name.")?.clone();
+        self.consume(TokenType::Left_Brace, "Expect '{' after enum name.")?;
+
+        let mut variants: Vec<Token> = Vec::new();
+        while !self.check(TokenType::Right_Brace) && !self.is_at_end() {
+            let variant = self.consume(TokenType::Identifier, "Expect variant name.")?.clone();
+            if variants.iter().any(|existing| existing.lexeme == variant.lexeme) {
+                let message = format!("Duplicate variant '{}' in enum '{}'.", variant.lexeme, name.lexeme);
+                return Err(self.error(&variant, &message));
+            }
+            variants.push(variant);
+            if !self.match_token(&[TokenType::Comma]) {
+                break;
+            }
         }
-        Ok(Stmt::Var { name, initializer })
+        self.consume(TokenType::Right_Brace, "Expect '}' after enum variants.")?;
+
+        if variants.is_empty() {
+            return Err(self.error(&name, "Enum must declare at least one variant."));
+        }
+        Ok(Stmt::Enum { name, variants })
     }
 
     fn statement(&mut self) -> Result<Stmt, ParseError> {
         if self.match_token(&[TokenType::Print]) {
             self.print_statement()
+        } else if self.match_token(&[TokenType::Match]) {
+            self.match_statement()
+        } else if self.match_token(&[TokenType::Return]) {
+            self.return_statement()
         } else if self.match_token(&[TokenType::Left_Brace]) {
             Ok(Stmt::Block(self.block()?))
+        } else if self.match_token(&[TokenType::If]) {
+            self.if_statement()
+        } else if self.match_token(&[TokenType::While]) {
+            self.while_statement()
+        } else if self.match_token(&[TokenType::For]) {
+            self.for_statement()
+        } else if self.match_token(&[TokenType::Break, TokenType::Continue]) {
+            self.loop_jump_statement()
+        } else if self.match_token(&[TokenType::Throw]) {
+            self.throw_statement()
+        } else if self.match_token(&[TokenType::Assert]) {
+            self.assert_statement()
+        } else if self.match_token(&[TokenType::Try]) {
+            self.try_statement()
         } else {
             self.expression_statement()
         }
     }
 
+    fn if_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.consume(TokenType::Left_Paren, "Expect '(' after 'if'.")?;
+        let condition = self.expression()?;
+        self.consume(TokenType::Right_Paren, "Expect ')' after if condition.")?;
+
+        let then_branch = Box::new(self.statement()?);
+        let else_branch = if self.match_token(&[TokenType::Else]) {
+            Some(Box::new(self.statement()?))
+        } else {
+            None
+        };
+
+        Ok(Stmt::If { condition, then_branch, else_branch })
+    }
+
+    fn while_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.consume(TokenType::Left_Paren, "Expect '(' after 'while'.")?;
+        let condition = self.expression()?;
+        self.consume(TokenType::Right_Paren, "Expect ')' after condition.")?;
+        let body = Box::new(self.loop_body()?);
+
+        Ok(Stmt::While { condition, body, increment: None })
+    }
+
+    // Desugars `for (init; cond; incr) body` into `{ init; while (cond) body }`, keeping
+    // `incr` on the loop so `continue` still runs it
+    fn for_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.consume(TokenType::Left_Paren, "Expect '(' after 'for'.")?;
+        if self.check(TokenType::Identifier) && self.check_next(TokenType::In) {
+            return self.for_in_statement();
+        }
+
+        let initializer = if self.match_token(&[TokenType::Semicolon]) {
+            None
+        } else {
+            let initializer = if self.match_token(&[TokenType::Var]) {
+                self.var_binding()?
+            } else {
+                Stmt::Expression(self.expression()?)
+            };
+            self.consume(TokenType::Semicolon, "Expect ';' after loop initializer.")?;
+            Some(initializer)
+        };
+
+        let condition = if self.check(TokenType::Semicolon) {
+            Expr::Literal(Literal::Bool(true))
+        } else {
+            self.expression()?
+        };
+        self.consume(TokenType::Semicolon, "Expect ';' after loop condition.")?;
+
+        let increment = if self.check(TokenType::Right_Paren) {
+            None
+        } else {
+            Some(self.expression()?)
+        };
+        self.consume(TokenType::Right_Paren, "Expect ')' after for clauses.")?;
+
+        let body = Box::new(self.loop_body()?);
+        let loop_stmt = Stmt::While { condition, body, increment };
+
+        Ok(match initializer {
+            Some(initializer) => Stmt::Block(vec![initializer, loop_stmt]),
+            None => loop_stmt,
+        })
+    }
+
+    fn for_in_statement(&mut self) -> Result<Stmt, ParseError> {
+        let name = self.advance().clone();
+        let keyword = self.advance().clone();
+        let iterable = self.assignment()?;
+        let end = if self.match_token(&[TokenType::Dot_Dot]) {
+            Some(self.assignment()?)
+        } else {
+            None
+        };
+        self.consume(TokenType::Right_Paren, "Expect ')' after for-in clause.")?;
+
+        let body = Box::new(self.loop_body()?);
+        Ok(Stmt::ForIn { name, keyword, iterable, end, body })
+    }
+
+    fn loop_body(&mut self) -> Result<Stmt, ParseError> {
+        self.loop_depth += 1;
+        let body = self.statement();
+        self.loop_depth -= 1;
+        body
+    }
+
+    fn loop_jump_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous().clone();
+        if self.loop_depth == 0 {
+            return Err(self.error(&keyword, &format!("Can't use '{}' outside of a loop.", keyword.lexeme)));
+        }
+
+        if self.require_semicolon {
+            self.consume(TokenType::Semicolon, &format!("Expect ';' after '{}'.", keyword.lexeme))?;
+        }
+        Ok(if keyword.token_type == TokenType::Break {
+            Stmt::Break(keyword)
+        } else {
+            Stmt::Continue(keyword)
+        })
+    }
+
+    fn throw_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous().clone();
+        let value = self.expression()?;
+        if self.require_semicolon {
+            self.consume(TokenType::Semicolon, "Expect ';' after thrown value.")?;
+        }
+        Ok(Stmt::Throw { keyword, value })
+    }
+
+    fn assert_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous().clone();
+        let start = self.peek().start;
+        // Not `expression()`: the comma separates the condition from the message
+        let condition = self.assignment()?;
+        let source = self.source.get(start..self.previous().end).unwrap_or_default().to_string();
+        let message = if self.match_token(&[TokenType::Comma]) {
+            Some(self.assignment()?)
+        } else {
+            None
+        };
+        if self.require_semicolon {
+            self.consume(TokenType::Semicolon, "Expect ';' after assertion.")?;
+        }
+        Ok(Stmt::Assert { keyword, condition, source, message })
+    }
+
+    fn try_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous().clone();
+        self.consume(TokenType::Left_Brace, "Expect '{' after 'try'.")?;
+        let body = self.block()?;
+
+        let catch = if self.match_token(&[TokenType::Catch]) {
+            self.consume(TokenType::Left_Paren, "Expect '(' after 'catch'.")?;
+            let name = self.consume(TokenType::Identifier, "Expect exception variable name.")?.clone();
+            self.consume(TokenType::Right_Paren, "Expect ')' after exception variable.")?;
+            self.consume(TokenType::Left_Brace, "Expect '{' before catch body.")?;
+            Some(CatchClause { name, body: self.block()? })
+        } else {
+            None
+        };
+
+        let finally = if self.match_token(&[TokenType::Finally]) {
+            self.consume(TokenType::Left_Brace, "Expect '{' after 'finally'.")?;
+            Some(self.block()?)
+        } else {
+            None
+        };
+
+        if catch.is_none() && finally.is_none() {
+            return Err(self.error(&keyword, "Expect 'catch' or 'finally' after try block."));
+        }
+        Ok(Stmt::Try { body, catch, finally })
+    }
+
     fn print_statement(&mut self) -> Result<Stmt, ParseError> {
         let value = self.expression()?;
         if self.require_semicolon {
@@ -291,6 +1084,16 @@ impl Parser {
     fn declaration(&mut self) -> Result<Stmt, ParseError> {
         if self.match_token(&[TokenType::Var]) {
             self.var_declaration()
+        } else if self.match_token(&[TokenType::Const]) {
+            self.const_declaration()
+        } else if self.match_token(&[TokenType::Import]) {
+            self.import_declaration()
+        } else if self.match_token(&[TokenType::Enum]) {
+            self.enum_declaration()
+        } else if self.check(TokenType::Fun) && self.check_next(TokenType::Identifier) {
+            // `fun name(...)` declares a function; a bare `fun (...)` is a lambda expression
+            self.advance();
+            self.function_declaration()
         } else {
             self.statement()
         }
@@ -0,0 +1,46 @@
+use std::str::Chars;
+
+/// Iterates over the characters of the source while keeping track of the byte offset and
+/// column of the next one, so positions cost nothing to look up while tokenizing.
+#[derive(Clone)]
+pub struct Cursor<'a> {
+    rest: Chars<'a>,
+    // The character `peek` returns, already taken from `rest`
+    current: Option<char>,
+    offset: usize,
+    column: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let mut rest = source.chars();
+        let current = rest.next();
+        Cursor { rest, current, offset: 0, column: 1 }
+    }
+
+    pub fn peek(&self) -> Option<&char> {
+        self.current.as_ref()
+    }
+
+    // Byte offset of the next character
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    // 1-based column of the next character, counted in characters
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl Iterator for Cursor<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.current?;
+        self.current = self.rest.next();
+        self.offset += c.len_utf8();
+        self.column = if c == '\n' { 1 } else { self.column + 1 };
+        Some(c)
+    }
+}
@@ -1,4 +1,6 @@
-pub mod token;   
+#[allow(clippy::module_inception)]
+pub mod token;
+mod cursor;
 pub mod tokenizer; 
 pub mod tokenizer_error;
 pub mod output;
@@ -1,20 +1,29 @@
 use std::fmt;
 
+use crate::number::format_float;
+
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
-    Left_Paren, Right_Paren, Left_Brace, Right_Brace,
-    Comma, Dot, Minus, Plus, Semicolon, Slash, Star,
+    Left_Paren, Right_Paren, Left_Brace, Right_Brace, Left_Bracket, Right_Bracket,
+    Comma, Dot, Minus, Plus, Semicolon, Colon, Question, Slash, Star, Percent,
+    Star_Star, Tilde_Slash,
+    Plus_Equal, Minus_Equal, Star_Equal, Slash_Equal,
+    Plus_Plus, Minus_Minus,
+    Ampersand, Pipe, Caret, Tilde,
 
     Bang, Bang_Equal,
     Equal, Equal_Equal,
     Greater, Greater_Equal,
     Less, Less_Equal,
+    Less_Less, Greater_Greater, Dot_Dot, Fat_Arrow, Question_Question, Question_Dot,
 
     Identifier, String, Number,
+    String_Head, String_Middle, String_Tail,
 
     And, Class, Else, False, Fun, For, If, Nil, Or,
     Print, Return, Super, This, True, Var, While,
+    Break, Continue, Throw, Try, Catch, Finally, Import, As, Const, Match, Enum, In, Assert,
 
     EOF,
 }
@@ -35,7 +44,9 @@ impl fmt::Display for TokenType {
 #[derive(Debug, Clone)]
 pub enum Literal {
     Str(String),
+    // Literals with a `.` or exponent are floats; all others are integers
     Num(f64),
+    Int(i64),
     Nil,
 }
 
@@ -45,6 +56,9 @@ pub struct Token {
     pub lexeme: String,
     pub literal: Literal,
     pub line: usize,
+    // Byte range of the lexeme in the source; empty for tokens the parser makes up
+    pub start: usize,
+    pub end: usize,
 }
 
 impl Token {
@@ -54,6 +68,8 @@ impl Token {
             lexeme,
             literal,
             line,
+            start: 0,
+            end: 0,
         }
     }
 }
@@ -62,7 +78,9 @@ impl fmt::Display for Token {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let literal_str = match &self.literal {
             Literal::Str(lit) => lit.clone(), 
-            Literal::Num(num) => format_number_test!(*num), 
+            Literal::Num(num) => format_float(*num),
+            // The token listing predates integers and shows every number literal as a float
+            Literal::Int(int) => format!("{}.0", int),
             Literal::Nil => "null".to_string(), 
         };
         
@@ -1,54 +1,129 @@
 use bytes::Bytes;
-use std::iter::Peekable;
 use crate::token::token::{Literal, Token, TokenType};
-use std::slice::Iter;
 use anyhow::Result;
+use unicode_xid::UnicodeXID;
+use super::cursor::Cursor;
 use super::tokenizer_error::TokenizerError;
 
 // Main tokenize function
 pub fn tokenize(contents: Bytes) ->Result<( Vec<Token>, Vec<TokenizerError>)> {
-    let mut chars = contents.iter().peekable(); // Use an iterator over bytes
+    // Decode the whole file up front so every later step works on whole characters
+    let source = match std::str::from_utf8(&contents) {
+        Ok(source) => source,
+        Err(err) => {
+            let (line, column) = position_of(&contents[..err.valid_up_to()]);
+            return Err(TokenizerError::InvalidUTF8 { line, column }.into());
+        }
+    };
+    let mut chars = Cursor::new(source);
     let mut line_number = 1;
     let mut tokens = Vec::new();
     let mut errors = Vec::new();
+    let mut interpolations: Vec<usize> = Vec::new(); // Brace depth of each open `${ ... }`
 
     while let Some(&c) = chars.peek() {
+        let start = chars.offset();
+        let scanned = tokens.len();
         match c {
-            b'"' => {
-                match parse_string(&mut chars, &mut tokens, line_number) {
-                    Ok(()) => {}, 
-                    Err(err) => {
-                        errors.push(err);
+            '"' if chars.clone().take(3).eq("\"\"\"".chars()) => {
+                parse_block_string(&mut chars, &mut tokens, &mut errors, &mut line_number)
+            }
+            'r' if starts_raw_string(&chars) => parse_raw_string(&mut chars, &mut tokens, &mut errors, &mut line_number),
+            '"' => {
+                if parse_string(&mut chars, &mut tokens, &mut errors, &mut line_number, false) {
+                    interpolations.push(0);
+                }
+            }
+            '0'..='9' => {
+                if let Err(err) = parse_number(&mut chars, &mut tokens, line_number) {
+                    errors.push(err);
+                }
+            }
+            '(' => push_simple_token(TokenType::Left_Paren, "(", &mut chars, &mut tokens, line_number),
+            ')' => push_simple_token(TokenType::Right_Paren, ")", &mut chars, &mut tokens, line_number),
+            '{' => {
+                if let Some(depth) = interpolations.last_mut() {
+                    *depth += 1;
+                }
+                push_simple_token(TokenType::Left_Brace, "{", &mut chars, &mut tokens, line_number)
+            }
+            '}' => match interpolations.last_mut() {
+                // A `}` at depth zero closes the interpolation and resumes the string
+                Some(0) => {
+                    interpolations.pop();
+                    if parse_string(&mut chars, &mut tokens, &mut errors, &mut line_number, true) {
+                        interpolations.push(0);
                     }
                 }
+                Some(depth) => {
+                    *depth -= 1;
+                    push_simple_token(TokenType::Right_Brace, "}", &mut chars, &mut tokens, line_number)
+                }
+                None => push_simple_token(TokenType::Right_Brace, "}", &mut chars, &mut tokens, line_number),
+            },
+            '[' => push_simple_token(TokenType::Left_Bracket, "[", &mut chars, &mut tokens, line_number),
+            ']' => push_simple_token(TokenType::Right_Bracket, "]", &mut chars, &mut tokens, line_number),
+            '*' if chars.clone().nth(1) == Some('=') => {
+                parse_double_char_token(&mut chars, &mut tokens, line_number, '=', TokenType::Star, TokenType::Star_Equal)
+            }
+            '*' => parse_double_char_token(&mut chars, &mut tokens, line_number, '*', TokenType::Star, TokenType::Star_Star),
+            '%' => push_simple_token(TokenType::Percent, "%", &mut chars, &mut tokens, line_number),
+            // `//` starts a comment, so integer division is spelled `~/`
+            '~' if chars.clone().nth(1) == Some('/') => {
+                chars.next();
+                push_simple_token(TokenType::Tilde_Slash, "~/", &mut chars, &mut tokens, line_number)
+            }
+            '~' => push_simple_token(TokenType::Tilde, "~", &mut chars, &mut tokens, line_number),
+            '&' => push_simple_token(TokenType::Ampersand, "&", &mut chars, &mut tokens, line_number),
+            '|' => push_simple_token(TokenType::Pipe, "|", &mut chars, &mut tokens, line_number),
+            '^' => push_simple_token(TokenType::Caret, "^", &mut chars, &mut tokens, line_number),
+            '.' => parse_double_char_token(&mut chars, &mut tokens, line_number, '.', TokenType::Dot, TokenType::Dot_Dot),
+            ',' => push_simple_token(TokenType::Comma, ",", &mut chars, &mut tokens, line_number),
+            '-' if chars.clone().nth(1) == Some('=') => {
+                parse_double_char_token(&mut chars, &mut tokens, line_number, '=', TokenType::Minus, TokenType::Minus_Equal)
+            }
+            '-' => parse_double_char_token(&mut chars, &mut tokens, line_number, '-', TokenType::Minus, TokenType::Minus_Minus),
+            '+' if chars.clone().nth(1) == Some('=') => {
+                parse_double_char_token(&mut chars, &mut tokens, line_number, '=', TokenType::Plus, TokenType::Plus_Equal)
+            }
+            '+' => parse_double_char_token(&mut chars, &mut tokens, line_number, '+', TokenType::Plus, TokenType::Plus_Plus),
+            ';' => push_simple_token(TokenType::Semicolon, ";", &mut chars, &mut tokens, line_number),
+            ':' => push_simple_token(TokenType::Colon, ":", &mut chars, &mut tokens, line_number),
+            '?' if chars.clone().nth(1) == Some('.') => {
+                parse_double_char_token(&mut chars, &mut tokens, line_number, '.', TokenType::Question, TokenType::Question_Dot)
+            }
+            '?' => parse_double_char_token(&mut chars, &mut tokens, line_number, '?', TokenType::Question, TokenType::Question_Question),
+            '=' if chars.clone().nth(1) == Some('>') => {
+                parse_double_char_token(&mut chars, &mut tokens, line_number, '>', TokenType::Equal, TokenType::Fat_Arrow)
+            }
+            '=' => parse_double_char_token(&mut chars, &mut tokens, line_number, '=', TokenType::Equal, TokenType::Equal_Equal),
+            '!' => parse_double_char_token(&mut chars, &mut tokens, line_number, '=', TokenType::Bang, TokenType::Bang_Equal),
+            '<' if chars.clone().nth(1) == Some('<') => {
+                parse_double_char_token(&mut chars, &mut tokens, line_number, '<', TokenType::Less, TokenType::Less_Less)
             }
-            b'0'..=b'9' => parse_number(&mut chars, &mut tokens, line_number),
-            b'(' => push_simple_token(TokenType::Left_Paren, "(", &mut chars, &mut tokens, line_number),
-            b')' => push_simple_token(TokenType::Right_Paren, ")", &mut chars, &mut tokens, line_number),
-            b'{' => push_simple_token(TokenType::Left_Brace, "{", &mut chars, &mut tokens, line_number),
-            b'}' => push_simple_token(TokenType::Right_Brace, "}", &mut chars, &mut tokens, line_number),
-            b'*' => push_simple_token(TokenType::Star, "*", &mut chars, &mut tokens, line_number),
-            b'.' => push_simple_token(TokenType::Dot, ".", &mut chars, &mut tokens, line_number),
-            b',' => push_simple_token(TokenType::Comma, ",", &mut chars, &mut tokens, line_number),
-            b'-' => push_simple_token(TokenType::Minus, "-", &mut chars, &mut tokens, line_number),
-            b'+' => push_simple_token(TokenType::Plus, "+", &mut chars, &mut tokens, line_number),
-            b';' => push_simple_token(TokenType::Semicolon, ";", &mut chars, &mut tokens, line_number),
-            b'=' => parse_double_char_token(&mut chars, &mut tokens, line_number, b'=', TokenType::Equal, TokenType::Equal_Equal),
-            b'!' => parse_double_char_token(&mut chars, &mut tokens, line_number, b'=', TokenType::Bang, TokenType::Bang_Equal),
-            b'<' => parse_double_char_token(&mut chars, &mut tokens, line_number, b'=', TokenType::Less, TokenType::Less_Equal),
-            b'>' => parse_double_char_token(&mut chars, &mut tokens, line_number, b'=', TokenType::Greater, TokenType::Greater_Equal),
-            b'/' => parse_slash(&mut chars, &mut tokens, line_number),
-            b' ' | b'\t' | b'\r' => { chars.next(); } // Ignore whitespace
-            b'\n' => { line_number += 1; chars.next(); } // Handle new lines
+            '>' if chars.clone().nth(1) == Some('>') => {
+                parse_double_char_token(&mut chars, &mut tokens, line_number, '>', TokenType::Greater, TokenType::Greater_Greater)
+            }
+            '<' => parse_double_char_token(&mut chars, &mut tokens, line_number, '=', TokenType::Less, TokenType::Less_Equal),
+            '>' => parse_double_char_token(&mut chars, &mut tokens, line_number, '=', TokenType::Greater, TokenType::Greater_Equal),
+            '/' => parse_slash(&mut chars, &mut tokens, line_number),
+            ' ' | '\t' | '\r' => { chars.next(); } // Ignore whitespace
+            '\n' => { line_number += 1; chars.next(); } // Handle new lines
             _ if is_alpha(c) => parse_identifier(&mut chars, &mut tokens, line_number),
             _ => {
-                errors.push(TokenizerError::UnexpectedCharacter {
-                    line: line_number,
-                    ch: *c as char,
-                });
+                errors.push(TokenizerError::UnexpectedCharacter { line: line_number, column: chars.column(), ch: c });
                 chars.next();
             }
         }
+
+        for token in &mut tokens[scanned..] {
+            token.start = start;
+            token.end = chars.offset();
+        }
+    }
+
+    if !interpolations.is_empty() {
+        errors.push(TokenizerError::UnterminatedInterpolation { line: line_number });
     }
 
     // Push EOF token after processing all characters
@@ -57,54 +132,67 @@ pub fn tokenize(contents: Bytes) ->Result<( Vec<Token>, Vec<TokenizerError>)> {
     Ok((tokens, errors))
 }
 
-fn push_simple_token(token_type: TokenType, lexeme: &str, chars: &mut Peekable<std::slice::Iter<u8>>, tokens: &mut Vec<Token>, line: usize) {
-    chars.next();  // Consume the byte
+// 1-based line and column just past the end of a prefix of the source, with columns
+// counted in characters rather than bytes
+fn position_of(prefix: &[u8]) -> (usize, usize) {
+    let prefix = String::from_utf8_lossy(prefix);
+    let line = prefix.matches('\n').count() + 1;
+    let column = prefix.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
+}
+
+fn push_simple_token(token_type: TokenType, lexeme: &str, chars: &mut Cursor, tokens: &mut Vec<Token>, line: usize) {
+    chars.next();  // Consume the character
     tokens.push(Token::new(token_type, lexeme.to_string(), Literal::Nil, line));
 }
 
 fn parse_double_char_token(
-    chars: &mut Peekable<std::slice::Iter<u8>>,
+    chars: &mut Cursor,
     tokens: &mut Vec<Token>,
     line: usize,
-    expected_next: u8,
+    expected_next: char,
     single_char_type: TokenType,
     double_char_type: TokenType,
 ) {
-    let mut lexeme = String::from_utf8(vec![*chars.next().unwrap()]).unwrap();  // Get the first byte and convert to string
-    if chars.peek() == Some(&&expected_next) {
-        lexeme.push(*chars.next().unwrap() as char);  // Append the second byte
+    let mut lexeme = chars.next().unwrap().to_string();  // Get the first character
+    if chars.peek() == Some(&expected_next) {
+        lexeme.push(chars.next().unwrap());  // Append the second character
         tokens.push(Token::new(double_char_type, lexeme, Literal::Nil, line));
     } else {
         tokens.push(Token::new(single_char_type, lexeme, Literal::Nil, line));
     }
 }
 
-fn parse_slash(chars: &mut Peekable<std::slice::Iter<u8>>, tokens: &mut Vec<Token>, line: usize) {
+fn parse_slash(chars: &mut Cursor, tokens: &mut Vec<Token>, line: usize) {
     chars.next();  // Consume '/'
-    if chars.peek() == Some(&&b'/') {
+    if chars.peek() == Some(&'/') {
         // It's a comment, consume until end of line
-        while let Some(&&next_char) = chars.peek() {
-            if next_char == b'\n' { break; }
+        while let Some(&next_char) = chars.peek() {
+            if next_char == '\n' { break; }
             chars.next();
         }
+    } else if chars.peek() == Some(&'=') {
+        chars.next();
+        tokens.push(Token::new(TokenType::Slash_Equal, "/=".to_string(), Literal::Nil, line));
     } else {
         tokens.push(Token::new(TokenType::Slash, "/".to_string(), Literal::Nil, line));
     }
 }
 
-fn is_alpha(c: &u8) -> bool {
-    c.is_ascii_alphabetic() || *c == b'_'
+// Identifiers follow Unicode XID rules, plus `_` as a start character
+fn is_alpha(c: char) -> bool {
+    c.is_xid_start() || c == '_'
 }
 
-fn is_alphanumeric(c: &u8) -> bool {
-    is_alpha(c) || c.is_ascii_digit()
+fn is_alphanumeric(c: char) -> bool {
+    c.is_xid_continue()
 }
 
-fn parse_identifier(chars: &mut Peekable<std::slice::Iter<u8>>, tokens: &mut Vec<Token>, line: usize) {
+fn parse_identifier(chars: &mut Cursor, tokens: &mut Vec<Token>, line: usize) {
     let mut identifier = String::new();
     while let Some(&c) = chars.peek() {
-        if is_alphanumeric(&c) {
-            identifier.push(*c as char);
+        if is_alphanumeric(c) {
+            identifier.push(c);
             chars.next();
         } else {
             break;
@@ -113,19 +201,32 @@ fn parse_identifier(chars: &mut Peekable<std::slice::Iter<u8>>, tokens: &mut Vec
 
     let token_type = match identifier.as_str() {
         "and" => TokenType::And,
+        "as" => TokenType::As,
+        "assert" => TokenType::Assert,
+        "break" => TokenType::Break,
+        "catch" => TokenType::Catch,
         "class" => TokenType::Class,
+        "const" => TokenType::Const,
+        "continue" => TokenType::Continue,
         "else" => TokenType::Else,
+        "enum" => TokenType::Enum,
         "false" => TokenType::False,
+        "finally" => TokenType::Finally,
         "for" => TokenType::For,
         "fun" => TokenType::Fun,
         "if" => TokenType::If,
+        "import" => TokenType::Import,
+        "in" => TokenType::In,
+        "match" => TokenType::Match,
         "nil" => TokenType::Nil,
         "or" => TokenType::Or,
         "print" => TokenType::Print,
         "return" => TokenType::Return,
         "super" => TokenType::Super,
         "this" => TokenType::This,
+        "throw" => TokenType::Throw,
         "true" => TokenType::True,
+        "try" => TokenType::Try,
         "var" => TokenType::Var,
         "while" => TokenType::While,
         _ => TokenType::Identifier,
@@ -134,86 +235,400 @@ fn parse_identifier(chars: &mut Peekable<std::slice::Iter<u8>>, tokens: &mut Vec
     tokens.push(Token::new(token_type, identifier, Literal::Nil, line));
 }
 
+// Parse a string literal, or the segment of an interpolated string that resumes after a
+// `}`. Returns true when the segment ends at a `${`, i.e. an interpolation was opened.
 fn parse_string(
-    chars: &mut Peekable<Iter<u8>>, 
+    chars: &mut Cursor, 
     tokens: &mut Vec<Token>, 
-    mut line: usize
-) -> Result<(), TokenizerError> {
-    let mut utf8_bytes = Vec::new();  // To collect the bytes for the string content
-    chars.next(); // Consume the opening quote 
+    errors: &mut Vec<TokenizerError>,
+    line: &mut usize,
+    continuation: bool,
+) -> bool {
+    let mut string_content = String::new();  // To collect the decoded string content
+    let mut lexeme = chars.next().unwrap().to_string();  // Opening quote or the `}` closing an interpolation
+    let mut escape_error = None;  // First invalid escape, reported once the segment is closed
 
     while let Some(&c) = chars.peek() {
-        if c == &b'"' {
-            chars.next(); // Consume the closing quote
+        let opens_interpolation = c == '$' && chars.clone().nth(1) == Some('{');
 
-            // Convert the collected bytes to a valid UTF-8 string
-            let string_content = match String::from_utf8(utf8_bytes) {
-                Ok(s) => s,
-                Err(_) => return Err(TokenizerError::InvalidUTF8String { line }),
+        if c == '"' || opens_interpolation {
+            chars.next(); // Consume the closing quote or the `$`
+            lexeme.push(c);
+            if opens_interpolation {
+                chars.next(); // Consume the `{`
+                lexeme.push('{');
+            }
+
+            let token_type = match (continuation, opens_interpolation) {
+                (false, false) => TokenType::String,
+                (false, true) => TokenType::String_Head,
+                (true, true) => TokenType::String_Middle,
+                (true, false) => TokenType::String_Tail,
             };
 
-            tokens.push(Token::new(
-                TokenType::String,
-                format!("\"{}\"", string_content),
-                Literal::Str(string_content),
-                line,
-            ));
-            return Ok(()); // Successfully parsed string
+            match escape_error {
+                Some(err) => errors.push(err),
+                None => tokens.push(Token::new(
+                    token_type,
+                    lexeme,
+                    Literal::Str(string_content),
+                    *line,
+                )),
+            }
+            return opens_interpolation;
         }
 
-        if c == &b'\n' {
-            // Allow newlines inside strings, increment line number
-            utf8_bytes.push(b'\n');  // Push newline to the byte buffer
-            line += 1;
-            chars.next();
+        if c == '\\' {
+            chars.next(); // Consume the backslash
+            lexeme.push('\\');
+            match parse_escape(chars, &mut lexeme, *line) {
+                Ok(decoded) => string_content.push(decoded),
+                Err(err) => {
+                    escape_error.get_or_insert(err);
+                }
+            }
             continue;
         }
 
-        if chars.peek().is_none() {
-            // If we run out of characters, it's an unterminated string
-            return Err(TokenizerError::UnterminatedString { line });
+        if c == '\n' {
+            // Allow newlines inside strings, increment line number
+            *line += 1;
         }
 
-        // Collect the byte for the string content
-        utf8_bytes.push(*c);
+        // Collect the character for the string content
+        string_content.push(c);
+        lexeme.push(c);
         chars.next(); 
     }
 
     // If we reach here, it means the string wasn't properly closed (unterminated)
-    Err(TokenizerError::UnterminatedString { line })
+    errors.push(TokenizerError::UnterminatedString { line: *line });
+    false
 }
 
-fn parse_number(chars: &mut Peekable<std::slice::Iter<u8>>, tokens: &mut Vec<Token>, line: usize) {
-    let mut value: f64 = 0.0;
-    let mut divisor: f64 = 1.0;
-    let mut is_fractional = false;
-    let mut lexeme = String::new();  // Capture the lexeme as it appears in the source
+// Whether the input is at `r"` or `r#...#"`, rather than an identifier starting with `r`
+fn starts_raw_string(chars: &Cursor) -> bool {
+    let mut lookahead = chars.clone().skip(1).skip_while(|&c| c == '#');
+    lookahead.next() == Some('"')
+}
 
-    while let Some(&c) = chars.peek() {
-        if c.is_ascii_digit() {
-            lexeme.push(*c as char);  // Append the current character to the lexeme
-            let digit = (c - b'0') as f64;
-            if is_fractional {
-                divisor *= 10.0;
-                value += digit / divisor;
-            } else {
-                value = value * 10.0 + digit;
-            }
-            chars.next();  // Move to the next character
-        } else if c == &b'.' {
-            if is_fractional {
-                break;  // Only allow one decimal point
-            }
-            is_fractional = true;
-            lexeme.push(*c as char);  // Append the decimal point to the lexeme
-            chars.next();
+// Parse `r"..."` or `r#"..."#`: no escapes or interpolation, and the closing quote must be
+// followed by as many `#` as the opening one was preceded by, so `"` can appear inside.
+fn parse_raw_string(
+    chars: &mut Cursor,
+    tokens: &mut Vec<Token>,
+    errors: &mut Vec<TokenizerError>,
+    line: &mut usize,
+) {
+    let start_line = *line;
+    let mut lexeme = chars.next().unwrap().to_string();  // The `r`
+    let mut hashes = 0;
+    while chars.peek() == Some(&'#') {
+        lexeme.push(chars.next().unwrap());
+        hashes += 1;
+    }
+    lexeme.push(chars.next().unwrap());  // The opening quote
+
+    let mut string_content = String::new();
+    while let Some(c) = chars.next() {
+        lexeme.push(c);
+        if c == '"' && chars.clone().take(hashes).filter(|&next| next == '#').count() == hashes {
+            for _ in 0..hashes {
+                lexeme.push(chars.next().unwrap());
+            }
+            tokens.push(Token::new(TokenType::String, lexeme, Literal::Str(string_content), *line));
+            return;
+        }
+        if c == '\n' {
+            *line += 1;
+        }
+        string_content.push(c);
+    }
+
+    errors.push(TokenizerError::UnterminatedRawString {
+        line: start_line,
+        delimiter: format!("\"{}", "#".repeat(hashes)),
+    });
+}
+
+// Parse a `"""` block string. Escapes work as in ordinary strings but `${` is literal.
+// A line break right after the opening quotes and the whitespace before the closing ones
+// are dropped, as is the indentation common to every non-blank line.
+fn parse_block_string(
+    chars: &mut Cursor,
+    tokens: &mut Vec<Token>,
+    errors: &mut Vec<TokenizerError>,
+    line: &mut usize,
+) {
+    let start_line = *line;
+    let mut lexeme: String = chars.by_ref().take(3).collect();
+    let mut body = String::new();
+
+    while let Some(c) = chars.next() {
+        lexeme.push(c);
+        if c == '"' && chars.clone().take(2).eq("\"\"".chars()) {
+            lexeme.extend(chars.by_ref().take(2));
+            match decode_escapes(&strip_indentation(&body), *line) {
+                Ok(string_content) => tokens.push(Token::new(
+                    TokenType::String,
+                    lexeme,
+                    Literal::Str(string_content),
+                    *line,
+                )),
+                Err(err) => errors.push(err),
+            }
+            return;
+        }
+        if c == '\\' {
+            // Keep the escaped character with its backslash so `\"` can't close the block
+            if let Some(escaped) = chars.next() {
+                if escaped == '\n' {
+                    *line += 1;
+                }
+                lexeme.push(escaped);
+                body.push(c);
+                body.push(escaped);
+            }
+            continue;
+        }
+        if c == '\n' {
+            *line += 1;
+        }
+        body.push(c);
+    }
+
+    errors.push(TokenizerError::UnterminatedBlockString { line: start_line });
+}
+
+// Remove the leading line break, trailing blank line and common indentation of a block string
+fn strip_indentation(body: &str) -> String {
+    let mut lines: Vec<&str> = body.split('\n').collect();
+    if lines.len() == 1 {
+        return body.to_string();
+    }
+    if lines[0].trim().is_empty() {
+        lines.remove(0);
+    }
+    if lines.len() > 1 && lines.last().is_some_and(|last| last.trim().is_empty()) {
+        lines.pop();
+    }
+
+    let indentation = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| if line.trim().is_empty() { "" } else { &line[indentation..] })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Decode the escape sequences left in a block string's text
+fn decode_escapes(text: &str, line: usize) -> Result<String, TokenizerError> {
+    let mut chars = Cursor::new(text);
+    let mut decoded = String::new();
+    let mut lexeme = String::new();  // Required by `parse_escape`, unused here
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            decoded.push(parse_escape(&mut chars, &mut lexeme, line)?);
         } else {
+            decoded.push(c);
+        }
+    }
+    Ok(decoded)
+}
+
+// Decode the escape sequence following a backslash, recording the consumed source text
+fn parse_escape(
+    chars: &mut Cursor,
+    lexeme: &mut String,
+    line: usize,
+) -> Result<char, TokenizerError> {
+    // Leave a closing quote or the end of input for `parse_string` to handle
+    let c = match chars.peek() {
+        Some(&c) if c != '\n' => c,
+        _ => return Err(TokenizerError::InvalidEscape { line, sequence: "\\".to_string() }),
+    };
+    chars.next();
+    lexeme.push(c);
+
+    match c {
+        'n' => Ok('\n'),
+        't' => Ok('\t'),
+        'r' => Ok('\r'),
+        '0' => Ok('\0'),
+        '\\' => Ok('\\'),
+        '"' => Ok('"'),
+        '\'' => Ok('\''),
+        '$' => Ok('$'),
+        'u' => parse_unicode_escape(chars, lexeme, line),
+        _ => Err(TokenizerError::InvalidEscape {
+            line,
+            sequence: format!("\\{}", c),
+        }),
+    }
+}
+
+// Decode the `{XXXX}` part of a `\u{XXXX}` escape into a Unicode scalar value
+fn parse_unicode_escape(
+    chars: &mut Cursor,
+    lexeme: &mut String,
+    line: usize,
+) -> Result<char, TokenizerError> {
+    if chars.peek() != Some(&'{') {
+        return Err(TokenizerError::InvalidUnicodeEscape { line, sequence: "\\u".to_string() });
+    }
+    chars.next();
+    lexeme.push('{');
+
+    let mut digits = String::new();
+    while let Some(&c) = chars.peek() {
+        if !c.is_ascii_hexdigit() {
             break;
         }
+        digits.push(c);
+        lexeme.push(c);
+        chars.next();
     }
 
+    let sequence = format!("\\u{{{}}}", digits);
+    if chars.peek() != Some(&'}') || digits.is_empty() || digits.len() > 6 {
+        return Err(TokenizerError::InvalidUnicodeEscape { line, sequence });
+    }
+    chars.next();
+    lexeme.push('}');
+
+    u32::from_str_radix(&digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or(TokenizerError::InvalidUnicodeEscape { line, sequence })
+}
+
+fn parse_number(
+    chars: &mut Cursor,
+    tokens: &mut Vec<Token>,
+    line: usize,
+) -> Result<(), TokenizerError> {
+    scan_number(chars, tokens, line).map_err(|err| match err {
+        // Swallow the rest of a malformed literal so it isn't re-tokenized as an identifier
+        TokenizerError::MalformedNumber { line, mut lexeme } => {
+            while let Some(&c) = chars.peek() {
+                if !is_alphanumeric(c) {
+                    break;
+                }
+                lexeme.push(c);
+                chars.next();
+            }
+            TokenizerError::MalformedNumber { line, lexeme }
+        }
+        err => err,
+    })
+}
+
+fn scan_number(
+    chars: &mut Cursor,
+    tokens: &mut Vec<Token>,
+    line: usize,
+) -> Result<(), TokenizerError> {
+    let mut lexeme = String::new();  // Capture the lexeme as it appears in the source
+
+    // Radix-prefixed integers: 0x1F, 0b1010, 0o17
+    let mut lookahead = chars.clone();
+    let radix = match (lookahead.next(), lookahead.next()) {
+        (Some('0'), Some('x' | 'X')) => Some(16),
+        (Some('0'), Some('b' | 'B')) => Some(2),
+        (Some('0'), Some('o' | 'O')) => Some(8),
+        _ => None,
+    };
+
+    let value = if let Some(radix) = radix {
+        lexeme.push(chars.next().unwrap());  // The `0`
+        lexeme.push(chars.next().unwrap());  // The prefix letter
+        let digits = scan_digits(chars, &mut lexeme, radix, line)?;
+        if digits.is_empty() {
+            return Err(TokenizerError::MalformedNumber { line, lexeme });
+        }
+        match i64::from_str_radix(&digits, radix) {
+            Ok(value) => Literal::Int(value),
+            Err(_) => return Err(TokenizerError::IntegerOverflow { line, lexeme }),
+        }
+    } else {
+        let mut digits = scan_digits(chars, &mut lexeme, 10, line)?;
+        let mut is_float = false;
+
+        // Only treat `.` as a decimal point when a digit follows, so `1.foo` and `0..10` still work
+        if chars.peek() == Some(&'.') && chars.clone().nth(1).is_some_and(|c| c.is_ascii_digit()) {
+            chars.next();
+            lexeme.push('.');
+            digits.push('.');
+            is_float = true;
+            digits.push_str(&scan_digits(chars, &mut lexeme, 10, line)?);
+        }
+
+        if let Some(&e @ ('e' | 'E')) = chars.peek() {
+            chars.next();
+            lexeme.push(e);
+            digits.push('e');
+            is_float = true;
+            if let Some(&sign @ ('+' | '-')) = chars.peek() {
+                chars.next();
+                lexeme.push(sign);
+                digits.push(sign);
+            }
+            let exponent = scan_digits(chars, &mut lexeme, 10, line)?;
+            if exponent.is_empty() {
+                return Err(TokenizerError::MalformedNumber { line, lexeme });
+            }
+            digits.push_str(&exponent);
+        }
+
+        if is_float {
+            match digits.parse::<f64>() {
+                Ok(value) => Literal::Num(value),
+                Err(_) => return Err(TokenizerError::MalformedNumber { line, lexeme }),
+            }
+        } else {
+            match digits.parse::<i64>() {
+                Ok(value) => Literal::Int(value),
+                Err(_) => return Err(TokenizerError::IntegerOverflow { line, lexeme }),
+            }
+        }
+    };
+
     // Push the token with the exact lexeme and literal value
-    tokens.push(Token::new(TokenType::Number, lexeme, Literal::Num(value), line));
+    tokens.push(Token::new(TokenType::Number, lexeme, value, line));
+    Ok(())
 }
 
+// Consume a run of digits in the given radix, allowing single `_` separators between digits.
+// Returns the digits with the separators removed.
+fn scan_digits(
+    chars: &mut Cursor,
+    lexeme: &mut String,
+    radix: u32,
+    line: usize,
+) -> Result<String, TokenizerError> {
+    let mut digits = String::new();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_digit(radix) {
+            digits.push(c);
+        } else if c == '_' {
+            let next_is_digit = chars.clone().nth(1).is_some_and(|next| next.is_digit(radix));
+            if digits.is_empty() || !next_is_digit {
+                lexeme.push('_');
+                chars.next();
+                return Err(TokenizerError::MalformedNumber { line, lexeme: lexeme.clone() });
+            }
+        } else {
+            break;
+        }
+        lexeme.push(c);
+        chars.next();
+    }
 
+    Ok(digits)
+}
@@ -5,9 +5,55 @@ pub enum TokenizerError {
     #[error("[line {line}] Error: Unterminated string.")]
     UnterminatedString { line: usize },
     
-    #[error("[line {line}] Error: Unexpected character: {ch}")]
-    UnexpectedCharacter { line: usize, ch: char },
+    #[error("[line {line}] Error: Unterminated raw string; expected a closing {delimiter}")]
+    UnterminatedRawString { line: usize, delimiter: String },
 
-    #[error("[line {line}] Error: Invalid UTF-8 sequence in string.")]
-    InvalidUTF8String { line: usize },
+    #[error("[line {line}] Error: Unterminated block string; expected a closing \"\"\"")]
+    UnterminatedBlockString { line: usize },
+
+    #[error("[line {line}] Error: Unterminated string interpolation.")]
+    UnterminatedInterpolation { line: usize },
+
+    #[error("[line {line}] Error: Malformed number literal: {lexeme}")]
+    MalformedNumber { line: usize, lexeme: String },
+
+    #[error("[line {line}] Error: Integer literal out of range: {lexeme}")]
+    IntegerOverflow { line: usize, lexeme: String },
+
+    #[error("[line {line}] Error: Unexpected character: {ch} (column {column})")]
+    UnexpectedCharacter { line: usize, column: usize, ch: char },
+
+    #[error("[line {line}] Error: Invalid UTF-8 sequence. (column {column})")]
+    InvalidUTF8 { line: usize, column: usize },
+
+    #[error("[line {line}] Error: Invalid escape sequence: {sequence}")]
+    InvalidEscape { line: usize, sequence: String },
+
+    #[error("[line {line}] Error: Invalid unicode escape sequence: {sequence}")]
+    InvalidUnicodeEscape { line: usize, sequence: String },
+}
+
+impl TokenizerError {
+    /// The source line the error was found on.
+    pub fn line(&self) -> usize {
+        match self {
+            TokenizerError::UnterminatedString { line }
+            | TokenizerError::UnterminatedRawString { line, .. }
+            | TokenizerError::UnterminatedBlockString { line }
+            | TokenizerError::UnterminatedInterpolation { line }
+            | TokenizerError::MalformedNumber { line, .. }
+            | TokenizerError::IntegerOverflow { line, .. }
+            | TokenizerError::UnexpectedCharacter { line, .. }
+            | TokenizerError::InvalidUTF8 { line, .. }
+            | TokenizerError::InvalidEscape { line, .. }
+            | TokenizerError::InvalidUnicodeEscape { line, .. } => *line,
+        }
+    }
+
+    /// The error without its "[line N] Error: " prefix, for reports that name the line themselves.
+    pub fn message(&self) -> String {
+        let text = self.to_string();
+        let prefix = format!("[line {}] Error: ", self.line());
+        text.strip_prefix(&prefix).map(str::to_string).unwrap_or(text)
+    }
 }
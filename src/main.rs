@@ -1,6 +1,6 @@
 use std::env;
 use std::fs;
-use std::io::{self, Write};
+use std::path::Path;
 use std::process::exit;
 use bytes::Bytes;
 
@@ -8,13 +8,21 @@ use codecraftersinterpreter::token::tokenizer::tokenize;
 use codecraftersinterpreter::parser::parser::Parser;
 use codecraftersinterpreter::parser::ast_printer::AstPrinter;
 use codecraftersinterpreter::interpreter::interpreter::Interpreter;
+use codecraftersinterpreter::interpreter::resolver::Resolver;
+use codecraftersinterpreter::interpreter::typechecker::TypeChecker;
 use codecraftersinterpreter::interpreter::runtime_error::InterpreterError;
 use codecraftersinterpreter::token::output::print_tokens_and_errors;
 
 
-fn run(source: Bytes, require_semicolon: bool, evaluate_mode: bool) -> Result<(), InterpreterError> {
+fn run(filename: &str, source: Bytes, require_semicolon: bool, evaluate_mode: bool, asserts_enabled: bool) -> Result<(), InterpreterError> {
     // Tokenize the source
-    let (tokens, errors) = tokenize(source).unwrap();
+    let (tokens, errors) = match tokenize(source.clone()) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Failed to tokenize input: {}", e);
+            exit(65);
+        }
+    };
 
     // Log tokenizer errors but do not exit immediately
     if !errors.is_empty() {
@@ -24,17 +32,32 @@ fn run(source: Bytes, require_semicolon: bool, evaluate_mode: bool) -> Result<()
     }
 
     // Pass the tokens to the parser, continue even if there were tokenizer errors
-    let mut parser = Parser::new(tokens, require_semicolon);
+    let mut parser = Parser::new(tokens, &String::from_utf8_lossy(&source), require_semicolon);
     let statements = match parser.parse() {
         Ok(statements) => statements,
         Err(e) => {
-            eprintln!("Parse error: {}", e);
+            eprintln!("{}", e);
             exit(65); // Exit with 65 for parse errors
         }
     };
 
+    // Report static errors, such as assigning to a constant, and warnings before running anything
+    let mut resolver = Resolver::new();
+    let resolved = resolver.resolve(&statements);
+    for warning in resolver.take_warnings() {
+        eprintln!("{}", warning);
+    }
+    if let Err(errors) = resolved {
+        for error in &errors {
+            eprintln!("{}", error);
+        }
+        exit(65);
+    }
+
     // Interpret the parsed statements
     let mut interpreter = Interpreter::new(evaluate_mode);
+    interpreter.set_current_file(Path::new(filename).to_path_buf());
+    interpreter.set_asserts_enabled(asserts_enabled);
     if let Err(e) = interpreter.interpret(statements) {
         eprintln!("Runtime error: {}", e);
         exit(70); // Exit with 70 for runtime errors
@@ -45,12 +68,12 @@ fn run(source: Bytes, require_semicolon: bool, evaluate_mode: bool) -> Result<()
 
 fn main() -> Result<(), InterpreterError> {
     // Log message at the start
-    writeln!(io::stderr(), "Logs from your program will appear here!").unwrap();
+    eprintln!("Logs from your program will appear here!");
 
     // Collect command-line arguments
     let args: Vec<String> = env::args().collect();
     if args.len() < 3 {
-        writeln!(io::stderr(), "Usage: {} <command> <filename>", args[0]).unwrap();
+        eprintln!("Usage: {} <command> <filename> [--no-asserts]", args[0]);
         return Ok(());
     }
 
@@ -58,9 +81,26 @@ fn main() -> Result<(), InterpreterError> {
     let command = &args[1];
     let filename = &args[2];
 
+    // Options follow the filename; misuse is a usage error (exit 64)
+    let mut asserts_enabled = true;
+    for option in &args[3..] {
+        match option.as_str() {
+            "--no-asserts" if matches!(command.as_str(), "run" | "evaluate") => asserts_enabled = false,
+            "--no-asserts" => {
+                eprintln!("Option --no-asserts only applies to the run and evaluate commands.");
+                exit(64);
+            }
+            _ => {
+                eprintln!("Unknown option: {}", option);
+                eprintln!("Usage: {} <command> <filename> [--no-asserts]", args[0]);
+                exit(64);
+            }
+        }
+    }
+
     // Read the file contents as bytes
     let file_bytes = fs::read(filename).unwrap_or_else(|_| {
-        writeln!(io::stderr(), "Failed to read file {}", filename).unwrap();
+        eprintln!("Failed to read file {}", filename);
         Vec::new()
     });
     let file_bytes = Bytes::from(file_bytes);  
@@ -85,7 +125,7 @@ fn main() -> Result<(), InterpreterError> {
 
         "parse" => {
             // Tokenize the input and parse the tokens
-            match tokenize(file_bytes) {
+            match tokenize(file_bytes.clone()) {
                 Ok((tokens, errors)) => {
                     if !errors.is_empty() {
                         for error in &errors {
@@ -95,7 +135,7 @@ fn main() -> Result<(), InterpreterError> {
                     }
 
                     // Parse the tokens and print the AST
-                    let mut parser = Parser::new(tokens, false);  
+                    let mut parser = Parser::new(tokens, &String::from_utf8_lossy(&file_bytes), false);  
                     match parser.parse() {
                         Ok(statements) => {
                             let printer = AstPrinter;
@@ -105,7 +145,7 @@ fn main() -> Result<(), InterpreterError> {
                             }
                         }
                         Err(e) => {
-                            eprintln!("Parse error: {}", e);
+                            eprintln!("{}", e);
                             exit(65);  
                         }
                     }
@@ -117,23 +157,56 @@ fn main() -> Result<(), InterpreterError> {
             }
         }
 
+        "typecheck" => {
+            // Check annotated types without running the program
+            match tokenize(file_bytes.clone()) {
+                Ok((tokens, errors)) => {
+                    if !errors.is_empty() {
+                        for error in &errors {
+                            eprintln!("{}", error);
+                        }
+                        exit(65);
+                    }
+
+                    let mut parser = Parser::new(tokens, &String::from_utf8_lossy(&file_bytes), true);
+                    let statements = match parser.parse() {
+                        Ok(statements) => statements,
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            exit(65);
+                        }
+                    };
+                    if let Err(errors) = TypeChecker::new().check(&statements) {
+                        for error in &errors {
+                            eprintln!("{}", error);
+                        }
+                        exit(65);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Failed to tokenize input: {}", e);
+                    exit(65);
+                }
+            }
+        }
+
         "evaluate" => {
             // Do not require semicolons in "evaluate" mode
-            if let Err(e) = run(file_bytes, false, true) {
+            if let Err(e) = run(filename, file_bytes, false, true, asserts_enabled) {
                 eprintln!("{}", e);
             }
         }
 
         "run" => {
             // Require semicolons in "run" mode
-            if let Err(e) = run(file_bytes, true, false) {
+            if let Err(e) = run(filename, file_bytes, true, false, asserts_enabled) {
                 eprintln!("{}", e);
             }
         }
 
 
         _ => {
-            writeln!(io::stderr(), "Unknown command: {}", command).unwrap();
+            eprintln!("Unknown command: {}", command);
         }
     }
 
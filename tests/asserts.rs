@@ -0,0 +1,73 @@
+// Behavior of `assert`: failure messages quote the condition as written and show the operands
+// of a comparison, and `--no-asserts` skips them. Also covers command-line option errors.
+
+mod common;
+
+use common::{lox_with, run};
+
+fn failure(source: &str) -> String {
+    let output = run(source);
+    assert_eq!(output.code, Some(70), "{}", source);
+    output.stderr.lines().find(|line| line.contains("assert")).unwrap_or_default().to_string()
+}
+
+#[test]
+fn failures_show_comparison_operands() {
+    assert_eq!(
+        failure("var x = 3; var y = 4;\nassert x == y;"),
+        "Runtime error: assert x == y failed: 3 != 4 [line 2]"
+    );
+    assert_eq!(
+        failure("var xs = [1, 2];\nassert len(xs) >= 3, \"too short\";"),
+        "Runtime error: assert len(xs) >= 3 failed: 2 < 3 (too short) [line 2]"
+    );
+}
+
+#[test]
+fn failures_quote_the_condition_straight_from_the_source() {
+    assert_eq!(
+        failure("assert   (1 + 2)  *  3 == 10 and true;"),
+        "Runtime error: assert (1 + 2)  *  3 == 10 and true failed [line 1]"
+    );
+    assert_eq!(
+        failure("var s = \"a\";\nassert s == \"b\" + \"${s}\", \"strings\";"),
+        "Runtime error: assert s == \"b\" + \"${s}\" failed: \"a\" != \"ba\" (strings) [line 2]"
+    );
+}
+
+#[test]
+fn passing_asserts_do_nothing() {
+    let output = run("assert 1 < 2; assert true, \"never shown\"; print \"ok\";");
+    assert_eq!(output.code, Some(0), "{}", output.stderr);
+    assert_eq!(output.stdout, "ok\n");
+}
+
+#[test]
+fn no_asserts_skips_assert_statements() {
+    let output = lox_with("run", "assert false, \"skipped\"; print \"after\";", &["--no-asserts"]);
+    assert_eq!(output.code, Some(0), "{}", output.stderr);
+    assert_eq!(output.stdout, "after\n");
+
+    let output = lox_with("evaluate", "1 + 2", &["--no-asserts"]);
+    assert_eq!(output.code, Some(0), "{}", output.stderr);
+    assert_eq!(output.stdout, "3\n");
+}
+
+#[test]
+fn option_misuse_exits_64() {
+    let output = lox_with("run", "print 1;", &["--fast"]);
+    assert_eq!(output.code, Some(64));
+    assert_eq!(output.stdout, "");
+    assert!(output.stderr.contains("Unknown option: --fast"), "{}", output.stderr);
+
+    for command in ["tokenize", "parse", "typecheck"] {
+        let output = lox_with(command, "print 1;", &["--no-asserts"]);
+        assert_eq!(output.code, Some(64), "{}", command);
+        assert!(
+            output.stderr.contains("Option --no-asserts only applies to the run and evaluate commands."),
+            "{}: {}",
+            command,
+            output.stderr
+        );
+    }
+}
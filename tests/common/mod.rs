@@ -0,0 +1,63 @@
+// Helpers shared by the integration tests: each one runs the interpreter binary on scripts
+// written to a temporary directory and captures what it prints.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SCRIPTS: AtomicUsize = AtomicUsize::new(0);
+
+pub struct Output {
+    pub stdout: String,
+    pub stderr: String,
+    pub code: Option<i32>,
+}
+
+// A fresh directory for the scripts of one test, so modules can import each other by relative path
+pub fn script_dir() -> PathBuf {
+    let id = SCRIPTS.fetch_add(1, Ordering::SeqCst);
+    let dir = std::env::temp_dir().join(format!("lox-tests-{}-{}", std::process::id(), id));
+    fs::create_dir_all(&dir).expect("create script directory");
+    dir
+}
+
+// Run the interpreter with `command` (run, tokenize, parse, ...), the script path and any options
+pub fn lox_file(command: &str, path: &Path, options: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_codecrafters-interpreter"))
+        .arg(command)
+        .arg(path)
+        .args(options)
+        .output()
+        .expect("run interpreter");
+    Output {
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        code: output.status.code(),
+    }
+}
+
+// Run `command` with options on a script written to a temporary file
+pub fn lox_with(command: &str, source: impl AsRef<[u8]>, options: &[&str]) -> Output {
+    let dir = script_dir();
+    let path = dir.join("main.lox");
+    fs::write(&path, source).expect("write script");
+    let output = lox_file(command, &path, options);
+    fs::remove_dir_all(&dir).ok();
+    output
+}
+
+pub fn lox(command: &str, source: impl AsRef<[u8]>) -> Output {
+    lox_with(command, source, &[])
+}
+
+pub fn run(source: &str) -> Output {
+    lox("run", source)
+}
+
+pub fn printed(source: &str) -> Vec<String> {
+    let output = run(source);
+    assert_eq!(output.code, Some(0), "script failed: {}", output.stderr);
+    output.stdout.lines().map(str::to_string).collect()
+}
@@ -0,0 +1,36 @@
+// Behavior of list and map destructuring in declarations and assignments, including the errors
+// that name the missing element.
+
+mod common;
+
+use common::{printed, run};
+
+#[test]
+fn lists_and_maps_destructure_into_variables() {
+    assert_eq!(
+        printed(r#"var [a, b] = [1, 2]; print a; print b; var {x, y} = {"y": 4, "x": 3}; print x - y;"#),
+        ["1", "2", "-1"]
+    );
+    // Extra list elements are ignored
+    assert_eq!(printed("var [first] = [1, 2, 3]; print first;"), ["1"]);
+}
+
+#[test]
+fn assignments_swap_values() {
+    assert_eq!(printed("var a = 1; var b = 2; [a, b] = [b, a]; print a; print b;"), ["2", "1"]);
+}
+
+#[test]
+fn shape_mismatches_name_the_missing_element() {
+    for (source, message) in [
+        ("var [a, b, c] = [1, 2];", "Cannot destructure 'c': the list has no element at index 2. [line 1]"),
+        ("var a; var b; [a, b] = [1];", "Cannot destructure 'b': the list has no element at index 1. [line 1]"),
+        (r#"var {x, z} = {"x": 1};"#, "Cannot destructure 'z': no such key or binding. [line 1]"),
+        ("var [a] = 5;", "Only lists can be destructured with this pattern. [line 1]"),
+        ("var {a} = [1];", "Only maps and modules can be destructured with this pattern. [line 1]"),
+    ] {
+        let output = run(source);
+        assert_eq!(output.code, Some(70), "{}", source);
+        assert!(output.stderr.contains(message), "{}: {}", source, output.stderr);
+    }
+}
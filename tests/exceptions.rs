@@ -0,0 +1,67 @@
+// Behavior of `throw` and `try`/`catch`/`finally`: which errors a catch clause sees, what it
+// binds, and how `finally` interacts with the pending outcome.
+
+mod common;
+
+use common::{printed, run};
+
+#[test]
+fn built_in_errors_are_caught_with_message_and_line() {
+    assert_eq!(
+        printed("try {\n  print 1 / 0;\n} catch (e) {\n  print e.message;\n  print e[\"line\"];\n}"),
+        ["Division by zero at line 2.", "2"]
+    );
+    assert_eq!(
+        printed("try { print missing; } catch (e) { print e.message; }"),
+        ["Undefined variable 'missing' at line 1."]
+    );
+}
+
+#[test]
+fn thrown_values_are_bound_as_they_are() {
+    assert_eq!(
+        printed(r#"try { throw {"code": 3}; } catch (e) { print e.code; } print "after";"#),
+        ["3", "after"]
+    );
+}
+
+#[test]
+fn catch_clauses_can_rethrow() {
+    assert_eq!(
+        printed(r#"try { try { throw "inner"; } catch (e) { throw e + "!"; } } catch (e) { print e; }"#),
+        ["inner!"]
+    );
+}
+
+#[test]
+fn finally_runs_on_every_path_and_can_override_return() {
+    assert_eq!(
+        printed("fun f() { try { return 1; } finally { print \"cleanup\"; } } print f();"),
+        ["cleanup", "1"]
+    );
+    assert_eq!(printed("fun f() { try { return 1; } finally { return 2; } } print f();"), ["2"]);
+    assert_eq!(
+        printed("fun f() { try { throw 1; } catch (e) { return e; } finally { print \"finally\"; } } print f();"),
+        ["finally", "1"]
+    );
+}
+
+#[test]
+fn uncaught_errors_still_exit_70() {
+    let output = run("throw \"boom\";");
+    assert_eq!(output.code, Some(70));
+    assert!(output.stderr.contains("Uncaught exception: boom [line 1]"), "{}", output.stderr);
+
+    let output = run("try { throw 1; } finally { print \"finally\"; }");
+    assert_eq!(output.code, Some(70));
+    assert_eq!(output.stdout, "finally\n");
+    assert!(output.stderr.contains("Uncaught exception: 1 [line 1]"), "{}", output.stderr);
+}
+
+#[test]
+fn failed_asserts_are_not_caught() {
+    let output = run("try { assert 1 == 2; } catch (e) { print \"caught\"; } finally { print \"finally\"; }");
+    assert_eq!(output.code, Some(70));
+    assert_eq!(output.stdout, "finally\n");
+    assert!(output.stderr.contains("assert 1 == 2 failed: 1 != 2 [line 1]"), "{}", output.stderr);
+}
@@ -0,0 +1,94 @@
+// Behavior of integer values: literals, arithmetic, promotion to floats, equality and hashing.
+// Each test runs the interpreter binary on a small script and checks what it prints.
+
+mod common;
+
+use common::{lox, printed, run};
+
+#[test]
+fn integer_arithmetic_stays_integral() {
+    assert_eq!(
+        printed("print 1 + 2; print 7 - 10; print 6 * 7; print 7 ~/ 2; print -7 % 3; print 2 ** 10;"),
+        ["3", "-3", "42", "3", "2", "1024"]
+    );
+}
+
+#[test]
+fn floats_promote_mixed_operands() {
+    assert_eq!(
+        printed("print 1 + 2.0; print 7 / 2; print 4 / 2; print 2 ** -1; print 99; print 99.0;"),
+        ["3.0", "3.5", "2.0", "0.5", "99", "99.0"]
+    );
+}
+
+#[test]
+fn overflow_is_a_runtime_error() {
+    for source in [
+        "print 9223372036854775807 + 1;",
+        "print -9223372036854775807 - 2;",
+        "print 4294967296 * 4294967296;",
+        "print 2 ** 63;",
+        "var x = -9223372036854775807 - 1; print -x;",
+    ] {
+        let output = run(source);
+        assert_eq!(output.code, Some(70), "{}", source);
+        assert!(output.stderr.contains("Integer overflow"), "{}: {}", source, output.stderr);
+    }
+}
+
+#[test]
+fn out_of_range_literal_is_rejected() {
+    let output = lox("tokenize", "9223372036854775808");
+    assert_eq!(output.code, Some(65));
+    assert!(output.stderr.contains("Integer literal out of range: 9223372036854775808"));
+}
+
+#[test]
+fn integers_equal_floats_of_the_same_value() {
+    assert_eq!(
+        printed("print 1 == 1.0; print 1.0 == 1; print 1 != 1.5; print 0 == -0.0;"),
+        ["true", "true", "true", "true"]
+    );
+}
+
+#[test]
+fn large_integers_compare_exactly_against_floats() {
+    // 2^53 + 1 has no exact float, so it must not equal the float 2^53
+    assert_eq!(
+        printed(
+            "var big = 9007199254740992;
+             print big + 1 == big + 1;
+             print big + 1 == 9007199254740992.0;
+             print big == 9007199254740992.0;
+             print big + 1 > 9007199254740992.0;"
+        ),
+        ["true", "false", "true", "true"]
+    );
+}
+
+#[test]
+fn integral_float_keys_find_integer_entries() {
+    assert_eq!(
+        printed(r#"var m = {1: "x"}; print m[1.0]; m[2.0] = "y"; print m[2]; print len(m); print has(m, 1.5);"#),
+        ["x", "y", "2", "false"]
+    );
+}
+
+#[test]
+fn tokenize_and_parse_keep_float_formatting() {
+    assert_eq!(lox("tokenize", "42 0x10").stdout, "NUMBER 42 42.0\nNUMBER 0x10 16.0\nEOF  null\n");
+    assert_eq!(lox("parse", "1 + 2").stdout, "(+ 1.0 2.0)\n");
+}
+
+#[test]
+fn left_shifts_that_lose_bits_overflow() {
+    assert_eq!(
+        printed("print 1 << 62; print -1 << 63; print 3 >> 1;"),
+        ["4611686018427387904", "-9223372036854775808", "1"]
+    );
+    for source in ["print 1 << 63;", "print 3 << 62;", "print -3 << 62;"] {
+        let output = run(source);
+        assert_eq!(output.code, Some(70), "{}", source);
+        assert!(output.stderr.contains("Integer overflow in '<<'"), "{}: {}", source, output.stderr);
+    }
+}
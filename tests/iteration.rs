@@ -0,0 +1,39 @@
+// Behavior of `for (x in ...)` loops over collections and ranges, and of the range() builtin.
+
+mod common;
+
+use common::{printed, run};
+
+#[test]
+fn collections_strings_and_ranges_are_iterable() {
+    assert_eq!(
+        printed(r#"for (x in [1, 2]) print x; for (k in {"a": 1}) print k; for (c in "hé") print c; for (i in 0..3) print i;"#),
+        ["1", "2", "a", "h", "é", "0", "1", "2"]
+    );
+    assert_eq!(printed("for (x in 0.5..3) print x;"), ["0.5", "1.5", "2.5"]);
+    assert_eq!(printed("print range(0, 10, 4); print range(1, 0, -0.5);"), ["[0, 4, 8]", "[1.0, 0.5]"]);
+}
+
+#[test]
+fn non_iterable_values_are_rejected() {
+    let output = run("for (x in 42) print x;");
+    assert_eq!(output.code, Some(70));
+    assert!(output.stderr.contains("can be iterated. [line 1]"), "{}", output.stderr);
+}
+
+#[test]
+fn huge_ranges_are_rejected_instead_of_running_forever() {
+    for source in ["for (x in 0.5..1e400) print x;", "for (x in 0.0..1e300) print x;"] {
+        let output = run(source);
+        assert_eq!(output.code, Some(70), "{}", source);
+        assert!(
+            output.stderr.contains("Float ranges must have finite bounds at most 10000000 apart. [line 1]"),
+            "{}: {}",
+            source,
+            output.stderr
+        );
+    }
+    let output = run("print range(0, 100000000, 1);");
+    assert_eq!(output.code, Some(70));
+    assert!(output.stderr.contains("range() would have more than 10000000 elements"), "{}", output.stderr);
+}
@@ -0,0 +1,36 @@
+// Behavior of `match`: literal, alternative, range and wildcard patterns, the unreachable-arm
+// warning, and malformed arms.
+
+mod common;
+
+use common::{lox, printed, run};
+
+#[test]
+fn arms_match_literals_alternatives_ranges_and_wildcards() {
+    assert_eq!(
+        printed(
+            "fun describe(x) {
+               match (x) { 1 => print \"one\", \"a\" | \"b\" => print \"letter\", 0..10 => print \"small\", _ => print \"other\" }
+             }
+             describe(1); describe(1.0); describe(\"b\"); describe(2.5); describe(10); describe(nil);"
+        ),
+        ["one", "one", "letter", "small", "other", "other"]
+    );
+    assert_eq!(printed("match (2) { 1 => print \"one\" } print \"no arm\";"), ["no arm"]);
+    assert_eq!(lox("parse", "match (1) { 1 => print \"one\" }").stdout, "(match 1.0 (1.0 => (print one)))\n");
+}
+
+#[test]
+fn arms_after_a_wildcard_are_flagged() {
+    let output = run("match (1) {\n  _ => print \"any\",\n  1 => print \"one\"\n}");
+    assert_eq!(output.code, Some(0), "{}", output.stderr);
+    assert_eq!(output.stdout, "any\n");
+    assert!(output.stderr.contains("Warning on line 3: Unreachable match arm after a '_' pattern."), "{}", output.stderr);
+}
+
+#[test]
+fn arms_need_an_arrow() {
+    let output = run("match (1) { 1 print \"one\" }");
+    assert_eq!(output.code, Some(65));
+    assert!(output.stderr.contains("Expect '=>' after match pattern."), "{}", output.stderr);
+}
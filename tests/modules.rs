@@ -0,0 +1,85 @@
+// Behavior of `import`: modules run once and are cached, cycles are detected, and errors in
+// an imported file name that file.
+
+mod common;
+
+use common::{lox_file, script_dir, Output};
+use std::fs;
+
+// Write each (name, source) file into a fresh directory and run the first one
+fn run_files(files: &[(&str, &str)]) -> Output {
+    let dir = script_dir();
+    for (name, source) in files {
+        fs::write(dir.join(name), source).expect("write script");
+    }
+    let output = lox_file("run", &dir.join(files[0].0), &[]);
+    fs::remove_dir_all(&dir).ok();
+    output
+}
+
+const UTIL: &str = "print \"loading util\";\nvar count = 0;\nfun bump() { count = count + 1; return count; }\n";
+
+#[test]
+fn modules_run_once_and_are_shared_between_imports() {
+    let output = run_files(&[
+        ("main.lox", "import \"util.lox\" as a;\nimport \"util.lox\" as b;\nprint a.bump();\nprint b.bump();"),
+        ("util.lox", UTIL),
+    ]);
+    assert_eq!(output.code, Some(0), "{}", output.stderr);
+    assert_eq!(output.stdout, "loading util\n1\n2\n");
+}
+
+#[test]
+fn paths_resolve_relative_to_the_importing_file() {
+    let dir = script_dir();
+    fs::create_dir(dir.join("lib")).expect("create lib directory");
+    fs::write(dir.join("main.lox"), "import \"lib/outer.lox\" as outer;\nprint outer.value;").unwrap();
+    fs::write(dir.join("lib/outer.lox"), "import \"inner.lox\" as inner;\nvar value = inner.value + 1;").unwrap();
+    fs::write(dir.join("lib/inner.lox"), "var value = 41;").unwrap();
+    let output = lox_file("run", &dir.join("main.lox"), &[]);
+    fs::remove_dir_all(&dir).ok();
+    assert_eq!(output.code, Some(0), "{}", output.stderr);
+    assert_eq!(output.stdout, "42\n");
+}
+
+#[test]
+fn import_cycles_are_reported() {
+    let output = run_files(&[
+        ("main.lox", "import \"a.lox\" as a;"),
+        ("a.lox", "import \"b.lox\" as b;"),
+        ("b.lox", "import \"a.lox\" as a;"),
+    ]);
+    assert_eq!(output.code, Some(70));
+    assert!(output.stderr.contains("Import cycle detected: "), "{}", output.stderr);
+    assert!(output.stderr.contains("a.lox -> "), "{}", output.stderr);
+}
+
+#[test]
+fn parse_errors_name_the_imported_file_and_its_line() {
+    let output = run_files(&[
+        ("main.lox", "print \"start\";\n\nimport \"bad.lox\" as bad;"),
+        ("bad.lox", "var ok = 1;\nvar x = ;\n"),
+    ]);
+    assert_eq!(output.code, Some(70));
+    assert_eq!(output.stdout, "start\n");
+    assert!(
+        output.stderr.contains("Error in imported file '") && output.stderr.contains("bad.lox' on line 2: Expect expression.\n"),
+        "{}",
+        output.stderr
+    );
+}
+
+#[test]
+fn import_failures_are_not_caught() {
+    let output = run_files(&[("main.lox", "try { import \"missing.lox\" as m; } catch (e) { print \"caught\"; }")]);
+    assert_eq!(output.code, Some(70));
+    assert_eq!(output.stdout, "");
+    assert!(output.stderr.contains("Cannot import 'missing.lox': "), "{}", output.stderr);
+}
+
+#[test]
+fn runtime_errors_in_modules_name_the_module() {
+    let output = run_files(&[("main.lox", "import \"fail.lox\" as fail;"), ("fail.lox", "\nprint 1 / 0;")]);
+    assert_eq!(output.code, Some(70));
+    assert!(output.stderr.contains("fail.lox: Division by zero at line 2."), "{}", output.stderr);
+}
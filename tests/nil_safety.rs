@@ -0,0 +1,42 @@
+// Behavior of the nil-safe operators: `??` falls back only on nil, and `?.` on a nil receiver
+// skips the rest of the property chain.
+
+mod common;
+
+use common::{lox, printed, run};
+
+#[test]
+fn coalescing_falls_back_only_on_nil() {
+    assert_eq!(
+        printed("var m = nil; print m ?? \"default\"; print false ?? \"x\"; print 0 ?? 1;"),
+        ["default", "false", "0"]
+    );
+    // The right operand is only evaluated when needed
+    assert_eq!(
+        printed("fun f() { print \"called\"; return 2; } print 1 ?? f(); print nil ?? f();"),
+        ["1", "called", "2"]
+    );
+}
+
+#[test]
+fn optional_chaining_short_circuits_on_nil() {
+    assert_eq!(
+        printed(r#"var m = nil; print m?.a; print m?.a.b.c; var p = {"a": {"b": 2}}; print p?.a.b; print p?.a?.b ?? 9;"#),
+        ["nil", "nil", "2", "2"]
+    );
+    assert_eq!(lox("parse", "a ?? b?.c.d").stdout, "(?? a (get (?. b c) d))\n");
+}
+
+#[test]
+fn only_nil_receivers_are_skipped() {
+    for source in ["var x = 1; print x?.a;", "var m = {\"a\": nil}; print m?.a.b;"] {
+        let output = run(source);
+        assert_eq!(output.code, Some(70), "{}", source);
+        assert!(
+            output.stderr.contains("Only maps, modules and enums have properties. [line 1]"),
+            "{}: {}",
+            source,
+            output.stderr
+        );
+    }
+}
@@ -0,0 +1,37 @@
+// Behavior of raw strings and triple-quoted block strings: delimiters, indentation stripping,
+// line accounting and unterminated literals.
+
+mod common;
+
+use common::{lox, printed, run};
+
+#[test]
+fn raw_strings_keep_backslashes_and_quotes() {
+    assert_eq!(
+        printed(r###"print r"C:\path\n"; print r#"say "hi""#; print r##"a "# b"##;"###),
+        [r"C:\path\n", r#"say "hi""#, r##"a "# b"##]
+    );
+    assert_eq!(lox("tokenize", r##"r#"a\"b"#"##).stdout, "STRING r#\"a\\\"b\"# a\\\"b\nEOF  null\n");
+}
+
+#[test]
+fn block_strings_strip_common_indentation() {
+    let source = "var s = \"\"\"\n    first\n      indented\n    last\n    \"\"\";\nprint s;\nprint missing;";
+    let output = run(source);
+    assert_eq!(output.stdout, "first\n  indented\nlast\n");
+    // Lines inside the block still count, so the error after it points at the right line
+    assert!(output.stderr.contains("Undefined variable 'missing' at line 7."), "{}", output.stderr);
+}
+
+#[test]
+fn unterminated_literals_are_errors() {
+    for (source, message) in [
+        ("r\"abc", "[line 1] Error: Unterminated raw string; expected a closing \""),
+        ("r#\"abc\"", "[line 1] Error: Unterminated raw string; expected a closing \"#"),
+        ("\"\"\"abc\ndef", "[line 1] Error: Unterminated block string; expected a closing \"\"\""),
+    ] {
+        let output = lox("tokenize", source);
+        assert_eq!(output.code, Some(65), "{}", source);
+        assert!(output.stderr.contains(message), "{}: {}", source, output.stderr);
+    }
+}
@@ -0,0 +1,101 @@
+// Behavior of the `typecheck` command: annotated mismatches are reported with their line
+// before anything runs, and unannotated code is accepted as it is.
+
+mod common;
+
+use common::{lox, printed};
+
+// The type errors reported for a script, which must fail with exit code 65
+fn type_errors(source: &str) -> Vec<String> {
+    let output = lox("typecheck", source);
+    assert_eq!(output.code, Some(65), "{}: {}", source, output.stderr);
+    output.stderr.lines().filter(|line| line.starts_with("Type error")).map(str::to_string).collect()
+}
+
+fn accepts(source: &str) {
+    let output = lox("typecheck", source);
+    assert_eq!(output.code, Some(0), "{}: {}", source, output.stderr);
+}
+
+#[test]
+fn annotations_check_initializers_and_assignments() {
+    assert_eq!(
+        type_errors("var n: number = \"one\";"),
+        ["Type error on line 1: Cannot initialize 'n' of type number with string."]
+    );
+    assert_eq!(
+        type_errors("var n: number = 1;\nn = true;"),
+        ["Type error on line 2: Cannot assign bool to 'n' of type number."]
+    );
+    assert_eq!(type_errors("var n: integer = 1;"), ["Type error on line 1: Unknown type 'integer'."]);
+}
+
+#[test]
+fn uninitialized_declarations_take_their_annotated_type() {
+    accepts("var n: number;\nn = 3;\nprint n;");
+    assert_eq!(
+        type_errors("var n: number;\nn = \"three\";"),
+        ["Type error on line 2: Cannot assign string to 'n' of type number."]
+    );
+}
+
+#[test]
+fn plus_takes_two_numbers_or_two_strings() {
+    accepts("var s: string = \"a\"; var n: number = 1; print s + s; print n + n;");
+    assert_eq!(
+        type_errors("var s: string = \"a\"; var n: number = 1;\nprint s + n;"),
+        ["Type error on line 2: Operands of '+' must be two numbers or two strings, found string and number."]
+    );
+}
+
+#[test]
+fn arithmetic_and_bitwise_operators_take_numbers() {
+    for operator in ["-", "*", "/", "%", "~/", "**", "<<", ">>", "^"] {
+        assert_eq!(
+            type_errors(&format!("var s: string = \"a\"; print s {} 1;", operator)),
+            [format!("Type error on line 1: Operands of '{}' must be numbers, found string and number.", operator)]
+        );
+    }
+}
+
+#[test]
+fn set_operators_take_two_sets() {
+    accepts("var a: set = set(); var b: set = set(); print a | b; print a & b; print a - b;");
+    assert_eq!(
+        type_errors("var a: set = set(); print a | 1;"),
+        ["Type error on line 1: Operands of '|' must be two numbers or two sets, found set and number."]
+    );
+}
+
+#[test]
+fn comparisons_take_numbers_and_equality_takes_anything() {
+    accepts("var s: string = \"a\"; var b: bool = s == 1; print s != nil;");
+    assert_eq!(
+        type_errors("var s: string = \"a\"; print s < 1;"),
+        ["Type error on line 1: Operands of '<' must be numbers, found string and number."]
+    );
+    assert_eq!(
+        type_errors("var b: bool = 1 > 2; var n: number = 1 >= 2;"),
+        ["Type error on line 1: Cannot initialize 'n' of type number with bool."]
+    );
+}
+
+#[test]
+fn unary_operators_follow_the_runtime_rules() {
+    accepts("var s: string = \"a\"; var b: bool = !s; print -1;");
+    assert_eq!(
+        type_errors("var s: string = \"a\"; print -s;"),
+        ["Type error on line 1: Operand of '-' must be a number, found string."]
+    );
+    assert_eq!(
+        type_errors("var s: string = \"a\"; s++;"),
+        ["Type error on line 1: Operand of '++' must be a number, found string."]
+    );
+}
+
+#[test]
+fn unannotated_code_is_accepted_unchanged() {
+    let source = "var x = \"a\";\nx = 1;\nprint x - 1;\nvar y;\ny = \"b\";\nfun twice(a) { return a * 2; }\nprint twice(3);";
+    accepts(source);
+    assert_eq!(printed(source), ["0", "6"]);
+}
@@ -0,0 +1,39 @@
+// Behavior of UTF-8 source handling: Unicode identifiers, whole-character error reports with
+// character-counted columns, and invalid UTF-8 input.
+
+mod common;
+
+use common::{lox, printed};
+
+#[test]
+fn unicode_identifiers_are_accepted() {
+    assert_eq!(printed("var café = 1; var 名前 = \"ü\"; print café; print 名前;"), ["1", "ü"]);
+    assert_eq!(
+        lox("tokenize", "var café = 1;").stdout,
+        "VAR var null\nIDENTIFIER café null\nEQUAL = null\nNUMBER 1 1.0\nSEMICOLON ; null\nEOF  null\n"
+    );
+}
+
+#[test]
+fn unexpected_characters_keep_the_line_prefix_and_report_columns() {
+    let output = lox("tokenize", "@");
+    assert_eq!(output.code, Some(65));
+    assert!(output.stderr.contains("[line 1] Error: Unexpected character: @ (column 1)\n"), "{}", output.stderr);
+
+    // Columns count characters, so the multi-byte letters before `€` count once each
+    let output = lox("tokenize", "var café = \"ü\";\nvar é = €;");
+    assert_eq!(output.code, Some(65));
+    assert!(output.stderr.contains("[line 2] Error: Unexpected character: € (column 9)\n"), "{}", output.stderr);
+    assert!(output.stdout.contains("IDENTIFIER é null\n"), "{}", output.stdout);
+}
+
+#[test]
+fn invalid_utf8_is_an_error() {
+    let output = lox("tokenize", b"var x = 1;\nprint \xff;");
+    assert_eq!(output.code, Some(65));
+    assert!(output.stderr.contains("[line 2] Error: Invalid UTF-8 sequence. (column 7)"), "{}", output.stderr);
+
+    let output = lox("run", b"print \"caf\xc3\";");
+    assert_eq!(output.code, Some(65));
+    assert!(output.stderr.contains("[line 1] Error: Invalid UTF-8 sequence. (column 11)"), "{}", output.stderr);
+}